@@ -0,0 +1,75 @@
+//! Compare `greatest` over many primitive columns with folding the columns pairwise using `keep_larger`,
+//! the previous implementation, which creates a comparison mask and a new array for every extra column.
+//!
+//! Run with `cargo bench --bench greatest --features bench`
+
+use datafusion::arrow::array::{Array, ArrayRef, Int64Array};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl};
+use datafusion_greatest::{keep_larger_pairwise, GreatestUdf, NullHandling};
+use rand::Rng;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const ROWS: usize = 8192;
+const ITERATIONS: u32 = 200;
+
+fn create_columns(count: usize, null_probability: f64) -> Vec<ArrayRef> {
+    let mut rng = rand::thread_rng();
+
+    (0..count)
+        .map(|_| {
+            let values = (0..ROWS)
+                .map(|_| if rng.gen_bool(null_probability) { None } else { Some(rng.gen::<i64>()) })
+                .collect::<Int64Array>();
+
+            Arc::new(values) as ArrayRef
+        })
+        .collect()
+}
+
+fn measure(mut f: impl FnMut()) -> Duration {
+    // Warmup
+    for _ in 0..10 {
+        f();
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let greatest = GreatestUdf::new();
+
+    for null_probability in [0.0, 0.1] {
+        for count in [2, 5, 20] {
+            let columns = create_columns(count, null_probability);
+            let args = columns.iter().cloned().map(ColumnarValue::Array).collect::<Vec<_>>();
+
+            // Make sure both implementations compute the same values, including how nulls are skipped
+            assert_eq!(
+                greatest.invoke(&args).unwrap().into_array(ROWS).unwrap().as_ref(),
+                keep_larger_pairwise(&columns, NullHandling::Skip).unwrap().as_ref(),
+            );
+
+            let single_pass = measure(|| {
+                black_box(greatest.invoke(black_box(&args)).unwrap());
+            });
+
+            let pairwise = measure(|| {
+                black_box(keep_larger_pairwise(black_box(&columns), NullHandling::Skip).unwrap().len());
+            });
+
+            println!(
+                "greatest of {count:>2} Int64 columns, {ROWS} rows, {:>3}% nulls: single pass {single_pass:>10.2?}, pairwise fold {pairwise:>10.2?} ({:.2}x)",
+                null_probability * 100.0,
+                pairwise.as_secs_f64() / single_pass.as_secs_f64(),
+            );
+        }
+    }
+}
@@ -1,11 +1,12 @@
-use crate::helpers::{find_coerced_type, keep_larger, keep_larger_scalar};
-use datafusion::arrow::array::{Array, ArrayRef};
+use crate::helpers::{check_comparable, find_coerced_type, impl_compare_options, keep_larger_bounds, keep_larger_columnar, keep_larger_nullable, keep_larger_output_ordering, keep_larger_propagate_constraints, simplify_args, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
 use datafusion::arrow::datatypes::DataType;
 use datafusion::error::Result;
-use datafusion::logical_expr::Volatility;
 use datafusion_common::plan_err;
 use datafusion_expr::sort_properties::{ExprProperties, SortProperties};
-use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use datafusion_expr::interval_arithmetic::Interval;
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
+use datafusion_expr::{ColumnarValue, Expr, ScalarUDFImpl, Signature};
 use std::any::Any;
 
 /// This example shows how to use the full ScalarUDFImpl API to implement a user
@@ -14,23 +15,41 @@ use std::any::Any;
 /// the power of the second argument `a^b`.
 ///
 /// To do so, we must implement the `ScalarUDFImpl` trait.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GreatestUdf {
     signature: Signature,
     aliases: Vec<String>,
+    options: CompareOptions,
 }
 
 impl GreatestUdf {
     /// Create a new instance of the `GreatestUdf` struct
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            signature: Signature::user_defined(
-                // Deterministic
-                Volatility::Immutable
-            ),
+            signature: user_defined_signature(),
             aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Larger, NullHandling::default(), NanOrdering::default()),
         }
     }
+
+    /// Return whether `greatest` can return NULL given whether each of its arguments can be NULL
+    ///
+    /// When NULL arguments are skipped the result is non-nullable if any of the arguments is non-nullable,
+    /// and when they are propagated it is non-nullable only if all the arguments are.
+    ///
+    /// DataFusion 41 always reports scalar functions as nullable and has no hook for a function to override it,
+    /// so use this to mark the output field as non-nullable where the schema is built (e.g. before writing to Parquet)
+    pub fn nullable(&self, args_nullable: &[bool]) -> bool {
+        keep_larger_nullable(args_nullable, self.options.null_handling)
+    }
+}
+
+impl_compare_options!(GreatestUdf, "arguments");
+
+impl Default for GreatestUdf {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScalarUDFImpl for GreatestUdf {
@@ -53,7 +72,7 @@ impl ScalarUDFImpl for GreatestUdf {
     /// this case it will always be a constant value, but it could also be a
     /// function of the input types.
     fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
-        find_coerced_type(arg_types).cloned()
+        find_coerced_type(self.name(), arg_types)
     }
 
     /// This is the function that actually calculates the results.
@@ -65,67 +84,13 @@ impl ScalarUDFImpl for GreatestUdf {
     /// However, it also means the implementation is more complex than when
     /// using `create_udf`.
     fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
-        // DataFusion has arranged for the correct inputs to be passed to this
-        // function, but we check again to make sure
-        assert!(args.len() >= 2);
-
-        // Split to scalars and arrays for later optimization
-        let (scalars, arrays): (Vec<_>, Vec<_>) = args.iter().partition(|x| match x {
-            ColumnarValue::Scalar(_) => true,
-            ColumnarValue::Array(_) => false,
-        });
-
-        let mut arrays_iter = arrays
-            .iter()
-            .map(|x| match x {
-                ColumnarValue::Array(a) => a,
-                _ => unreachable!(),
-            });
-
-        let first_array = arrays_iter.next();
-
-        let mut largest: ArrayRef;
-
-        // Optimization: merge all scalars into one to avoid recomputing
-        if !scalars.is_empty() {
-            let mut scalars_iter = scalars
-                .iter()
-                .map(|x| match x {
-                    ColumnarValue::Scalar(s) => s,
-                    _ => unreachable!(),
-                });
-
-            // We have at least one scalar
-            let mut largest_scalar = scalars_iter.next().unwrap();
-
-            for scalar in scalars_iter {
-                largest_scalar = keep_larger_scalar(largest_scalar, scalar)?;
-            }
-
-            // If we only have scalars, return the largest one
-            if arrays.is_empty() {
-                return Ok(ColumnarValue::Scalar(largest_scalar.clone()));
-            }
-
-            // We have at least one array
-            let first_array = first_array.unwrap();
-
-            // Start with the largest value
-            largest = keep_larger(
-                first_array.clone(),
-                largest_scalar.to_array_of_size(first_array.len())?
-            )?;
-        } else {
-            // If we only have arrays, start with the first array
-            // (We must have at least one array)
-            largest = first_array.unwrap().clone();
-        }
-
-        for array in arrays_iter {
-            largest = keep_larger(array.clone(), largest)?;
-        }
+        keep_larger_columnar(args, self.options)
+    }
 
-        Ok(ColumnarValue::Array(largest))
+    /// Flatten nested `greatest` calls, fold the literal arguments and remove the redundant ones,
+    /// for example `greatest(greatest(a, 1), NULL, a, 2)` is simplified to `greatest(a, 2)`
+    fn simplify(&self, args: Vec<Expr>, info: &dyn SimplifyInfo) -> Result<ExprSimplifyResult> {
+        simplify_args(self, args, info, self.options)
     }
 
     /// We will also add an alias of "my_greatest"
@@ -133,9 +98,22 @@ impl ScalarUDFImpl for GreatestUdf {
         &self.aliases
     }
 
+    /// The result is ordered only if all the non-constant arguments are ordered the same way
+    /// and NULL and NaN values are sorted where they would be compared
     fn output_ordering(&self, input: &[ExprProperties]) -> Result<SortProperties> {
-        // the greatest preserves the order of the input as it doesn't matter
-        Ok(input[0].sort_properties)
+        Ok(keep_larger_output_ordering(input, self.options))
+    }
+
+    /// The result is at most the largest upper bound of the arguments,
+    /// and at least the largest lower bound when NULL is propagated (or the smallest one when NULL is skipped)
+    fn evaluate_bounds(&self, input: &[&Interval]) -> Result<Interval> {
+        keep_larger_bounds(input, self.options.null_handling)
+    }
+
+    /// Narrow the arguments bounds given the result bounds,
+    /// for example `greatest(a, b) <= 10` means that both `a <= 10` and `b <= 10`
+    fn propagate_constraints(&self, interval: &Interval, inputs: &[&Interval]) -> Result<Option<Vec<Interval>>> {
+        keep_larger_propagate_constraints(interval, inputs, self.options.null_handling)
     }
 
     /// What types can this function coerce its arguments to?
@@ -151,9 +129,19 @@ impl ScalarUDFImpl for GreatestUdf {
 
         // Make sure we can do the comparison,
         // similar to: https://github.com/apache/spark/blob/19aad9ee36edad0906b8223074351bfb76237c0a/sql/catalyst/src/main/scala/org/apache/spark/sql/catalyst/expressions/arithmetic.scala#L1287-L1295
-        let coerced_type = find_coerced_type(input_types)?;
+        let coerced_type = find_coerced_type(self.name(), input_types)?;
+
+        // Fail when planning if the values can't be ordered, instead of when executing
+        check_comparable(self.name(), &coerced_type)?;
 
-        Ok(vec![coerced_type.clone(); input_types.len()])
+        Ok(vec![coerced_type; input_types.len()])
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
     }
-}
 
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}
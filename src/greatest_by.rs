@@ -0,0 +1,110 @@
+use crate::helpers::{check_comparable, find_coerced_type, impl_compare_options, keep_larger_by_columnar, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::{exec_err, plan_err};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use std::any::Any;
+
+/// Return the value that is at the same position as the greatest key, or NULL when the greatest key is NULL.
+///
+/// The arguments are N keys followed by N values, for example `greatest_by(rating_a, rating_b, price_a, price_b)`
+/// is the price of the vendor with the highest rating.
+/// The keys share the same comparison, coercion, null and NaN semantics as [`GreatestUdf`](crate::GreatestUdf),
+/// ties are resolved to the key with the lowest position, the same as [`GreatestIndexUdf`](crate::GreatestIndexUdf).
+/// The values are coerced to a common type of their own, which may be different from the type of the keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreatestByUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+    options: CompareOptions,
+}
+
+impl GreatestByUdf {
+    /// Create a new instance of the `GreatestByUdf` struct
+    pub fn new() -> Self {
+        Self {
+            signature: user_defined_signature(),
+            aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Larger, NullHandling::default(), NanOrdering::default()),
+        }
+    }
+}
+
+impl_compare_options!(GreatestByUdf, "keys");
+
+impl Default for GreatestByUdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for GreatestByUdf {
+    /// We implement as_any so that we can downcast the ScalarUDFImpl trait object
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Return the name of this function
+    fn name(&self) -> &str {
+        "greatest_by"
+    }
+
+    /// Return the "signature" of this function -- namely what types of arguments it will take
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The return type is the common type of the values, the second half of the arguments
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        find_coerced_type(self.name(), &arg_types[arg_types.len() / 2..])
+    }
+
+    /// Calculate the value of the greatest key at each row
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        // DataFusion has arranged for the correct inputs to be passed to this
+        // function, but we check again to make sure, as the function can also be invoked directly
+        if !args.len().is_multiple_of(2) {
+            return exec_err!("Expected the same number of keys and values, got {} arguments", args.len());
+        }
+
+        let (keys, values) = args.split_at(args.len() / 2);
+
+        keep_larger_by_columnar(keys, values, self.options)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// The keys are coerced to the same type as greatest so they can be compared,
+    /// and the values are coerced to a common type of their own
+    fn coerce_types(&self, input_types: &[DataType]) -> Result<Vec<DataType>> {
+        // make sure that there are at least 2 keys, same as greatest, and a value for each key
+        if input_types.len() < 4 || !input_types.len().is_multiple_of(2) {
+            return plan_err!(
+                "greatest_by was called with {} arguments. It requires at least 2 keys followed by the same number of values.",
+                input_types.len()
+            );
+        }
+
+        let (key_types, value_types) = input_types.split_at(input_types.len() / 2);
+
+        let coerced_key_type = find_coerced_type(self.name(), key_types)?;
+
+        // Fail when planning if the keys can't be ordered, instead of when executing
+        check_comparable(self.name(), &coerced_key_type)?;
+
+        let coerced_value_type = find_coerced_type(self.name(), value_types)?;
+
+        Ok([vec![coerced_key_type; key_types.len()], vec![coerced_value_type; value_types.len()]].concat())
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
+    }
+
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::helpers::{check_comparable, find_coerced_type, impl_compare_options, keep_larger_index_columnar, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::plan_err;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use std::any::Any;
+
+/// Return the 1-based position of the argument that holds the greatest value,
+/// or NULL when the greatest value is NULL (e.g. all the arguments are NULL).
+///
+/// This takes the same arguments as [`GreatestUdf`](crate::GreatestUdf) and share the same
+/// comparison, coercion, null and NaN semantics, ties are resolved to the lowest position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreatestIndexUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+    options: CompareOptions,
+}
+
+impl GreatestIndexUdf {
+    /// Create a new instance of the `GreatestIndexUdf` struct
+    pub fn new() -> Self {
+        Self {
+            signature: user_defined_signature(),
+            aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Larger, NullHandling::default(), NanOrdering::default()),
+        }
+    }
+}
+
+impl_compare_options!(GreatestIndexUdf, "arguments");
+
+impl Default for GreatestIndexUdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for GreatestIndexUdf {
+    /// We implement as_any so that we can downcast the ScalarUDFImpl trait object
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Return the name of this function
+    fn name(&self) -> &str {
+        "greatest_index"
+    }
+
+    /// Return the "signature" of this function -- namely what types of arguments it will take
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The return type is always the position of the argument
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    /// Calculate the position of the greatest value at each row
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        keep_larger_index_columnar(args, self.options)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// What types can this function coerce its arguments to?
+    fn coerce_types(&self, input_types: &[DataType]) -> Result<Vec<DataType>> {
+        // make sure that the input types has at least 2 elements, same as greatest
+        if input_types.len() < 2 {
+            return plan_err!(
+                "greatest_index was called with {} arguments. It requires at least 2.",
+                input_types.len()
+            );
+        }
+
+        // Arguments are coerced to the same type as greatest so they can be compared
+        let coerced_type = find_coerced_type(self.name(), input_types)?;
+
+        // Fail when planning if the values can't be ordered, instead of when executing
+        check_comparable(self.name(), &coerced_type)?;
+
+        Ok(vec![coerced_type; input_types.len()])
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
+    }
+
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}
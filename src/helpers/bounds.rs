@@ -0,0 +1,223 @@
+use crate::NullHandling;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::ScalarValue;
+use datafusion_expr::interval_arithmetic::Interval;
+
+/// Return the interval of the largest value of the inputs
+///
+/// The upper bound is the largest upper bound.
+/// When propagating nulls the output has a value only when all the inputs have one, so the lower bound is the largest lower bound,
+/// but when skipping nulls any of the inputs can be NULL, so the lower bound is the smallest lower bound.
+pub(crate) fn keep_larger_bounds(input: &[&Interval], null_handling: NullHandling) -> Result<Interval> {
+    let Some(data_type) = common_data_type(input) else {
+        return Interval::make_unbounded(&DataType::Null);
+    };
+
+    let mut lower = input[0].lower().clone();
+    let mut upper = input[0].upper().clone();
+
+    for interval in &input[1..] {
+        lower = match null_handling {
+            NullHandling::Skip => min_of_lower_bounds(lower, interval.lower()),
+            NullHandling::Propagate => max_of_lower_bounds(lower, interval.lower()),
+        };
+        upper = max_of_upper_bounds(upper, interval.upper());
+    }
+
+    // NULL bound means unbounded, make sure it has the correct type
+    if lower.is_null() {
+        lower = ScalarValue::try_from(&data_type)?;
+    }
+    if upper.is_null() {
+        upper = ScalarValue::try_from(&data_type)?;
+    }
+
+    Interval::try_new(lower, upper)
+}
+
+/// Update the intervals of the inputs given the interval of the largest value of them
+///
+/// - All the inputs must be less than or equal to the upper bound of the output
+/// - If only one input can reach the lower bound of the output, it must be greater than or equal to it
+///
+/// Return `None` if the output interval can't be reached
+pub(crate) fn keep_larger_propagate_constraints(interval: &Interval, inputs: &[&Interval], null_handling: NullHandling) -> Result<Option<Vec<Interval>>> {
+    let Some(data_type) = common_data_type(inputs).filter(|data_type| *data_type == interval.data_type()) else {
+        return Ok(Some(vec![]));
+    };
+
+    let unbounded = ScalarValue::try_from(&data_type)?;
+    let at_most_upper = Interval::try_new(unbounded.clone(), interval.upper().clone())?;
+
+    let mut new_inputs = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        match (input.intersect(&at_most_upper)?, null_handling) {
+            (Some(new_input), _) => new_inputs.push(new_input),
+
+            // The input is larger than the upper bound, so the output interval can only be reached when it is NULL
+            (None, NullHandling::Skip) => new_inputs.push((*input).clone()),
+            (None, NullHandling::Propagate) => return Ok(None),
+        }
+    }
+
+    let reach_lower = new_inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| input.upper().is_null() || interval.lower().is_null() || input.upper() >= interval.lower())
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    match reach_lower.as_slice() {
+        // None of the inputs can be large enough
+        [] => return Ok(None),
+
+        // The only input that can be large enough must be the largest
+        [index] => {
+            let at_least_lower = Interval::try_new(interval.lower().clone(), unbounded)?;
+
+            match new_inputs[*index].intersect(&at_least_lower)? {
+                Some(new_input) => new_inputs[*index] = new_input,
+                None => return Ok(None),
+            }
+        }
+        _ => {}
+    }
+
+    Ok(Some(new_inputs))
+}
+
+/// Return the data type of the intervals if they all have the same one
+fn common_data_type(intervals: &[&Interval]) -> Option<DataType> {
+    let data_type = intervals.first()?.data_type();
+
+    intervals
+        .iter()
+        .all(|interval| interval.data_type() == data_type)
+        .then_some(data_type)
+}
+
+/// NULL lower bound means unbounded, so it is the smallest
+fn max_of_lower_bounds(lhs: ScalarValue, rhs: &ScalarValue) -> ScalarValue {
+    if lhs.is_null() || (!rhs.is_null() && *rhs > lhs) {
+        rhs.clone()
+    } else {
+        lhs
+    }
+}
+
+/// NULL lower bound means unbounded, so it is the smallest
+fn min_of_lower_bounds(lhs: ScalarValue, rhs: &ScalarValue) -> ScalarValue {
+    if rhs.is_null() || (!lhs.is_null() && *rhs < lhs) {
+        rhs.clone()
+    } else {
+        lhs
+    }
+}
+
+/// NULL upper bound means unbounded, so it is the largest
+fn max_of_upper_bounds(lhs: ScalarValue, rhs: &ScalarValue) -> ScalarValue {
+    if rhs.is_null() || (!lhs.is_null() && *rhs > lhs) {
+        rhs.clone()
+    } else {
+        lhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(lower: Option<i32>, upper: Option<i32>) -> Interval {
+        Interval::make(lower, upper).unwrap()
+    }
+
+    #[test]
+    fn bounds_when_propagating_nulls() {
+        let a = interval(Some(1), Some(10));
+        let b = interval(Some(5), Some(8));
+        let c = interval(None, Some(3));
+
+        assert_eq!(keep_larger_bounds(&[&a, &b, &c], NullHandling::Propagate).unwrap(), interval(Some(5), Some(10)));
+
+        let d = interval(Some(0), None);
+
+        assert_eq!(keep_larger_bounds(&[&a, &d], NullHandling::Propagate).unwrap(), interval(Some(1), None));
+    }
+
+    #[test]
+    fn bounds_when_skipping_nulls() {
+        let a = interval(Some(1), Some(10));
+        let b = interval(Some(5), Some(8));
+
+        assert_eq!(keep_larger_bounds(&[&a, &b], NullHandling::Skip).unwrap(), interval(Some(1), Some(10)));
+
+        let c = interval(None, Some(3));
+
+        assert_eq!(keep_larger_bounds(&[&a, &b, &c], NullHandling::Skip).unwrap(), interval(None, Some(10)));
+    }
+
+    #[test]
+    fn bounds_of_different_types_are_unbounded() {
+        let a = interval(Some(1), Some(10));
+        let b = Interval::make(Some(5i64), Some(8i64)).unwrap();
+
+        assert_eq!(keep_larger_bounds(&[&a, &b], NullHandling::Propagate).unwrap(), Interval::make_unbounded(&DataType::Null).unwrap());
+    }
+
+    #[test]
+    fn propagate_upper_bound_to_all_inputs() {
+        let a = interval(Some(0), Some(20));
+        let b = interval(Some(5), Some(30));
+
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(None, Some(10)), &[&a, &b], NullHandling::Skip).unwrap(),
+            Some(vec![interval(Some(0), Some(10)), interval(Some(5), Some(10))])
+        );
+    }
+
+    #[test]
+    fn propagate_lower_bound_to_the_only_input_that_can_reach_it() {
+        let a = interval(Some(0), Some(20));
+        let b = interval(Some(5), Some(8));
+
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(Some(10), None), &[&a, &b], NullHandling::Skip).unwrap(),
+            Some(vec![interval(Some(10), Some(20)), interval(Some(5), Some(8))])
+        );
+
+        // Both can reach the lower bound, so nothing is known
+        let c = interval(Some(0), Some(15));
+
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(Some(10), None), &[&a, &c], NullHandling::Skip).unwrap(),
+            Some(vec![a.clone(), c.clone()])
+        );
+    }
+
+    #[test]
+    fn propagate_infeasible_interval() {
+        let a = interval(Some(0), Some(5));
+        let b = interval(Some(1), Some(8));
+
+        // None of the inputs can be 10 or above
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(Some(10), None), &[&a, &b], NullHandling::Skip).unwrap(),
+            None
+        );
+
+        // b is always above 0, so it can only be NULL when skipping nulls
+        let a = interval(Some(-5), Some(5));
+        let b = interval(Some(1), Some(8));
+
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(None, Some(0)), &[&a, &b], NullHandling::Skip).unwrap(),
+            Some(vec![interval(Some(-5), Some(0)), b.clone()])
+        );
+        assert_eq!(
+            keep_larger_propagate_constraints(&interval(None, Some(0)), &[&a, &b], NullHandling::Propagate).unwrap(),
+            None
+        );
+    }
+}
@@ -1,16 +1,44 @@
-use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::datatypes::{DataType, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL256_MAX_PRECISION};
 use datafusion_expr::type_coercion::functions::can_coerce_from;
-use datafusion::error::Result;
+use datafusion::error::{DataFusionError, Result};
 use datafusion_common::plan_err;
 
-pub(crate) fn find_coerced_type(data_types: &[DataType]) -> Result<&DataType> {
+/// Return the type that all the arguments of the function `name` are coerced to
+///
+/// When there is no such type, the error names the arguments that conflict with the type of most of the arguments
+/// and suggests how to cast them
+pub(crate) fn find_coerced_type(name: &str, data_types: &[DataType]) -> Result<DataType> {
+    find_common_type(data_types).map_err(|error| incompatible_arguments_error(name, data_types, error))
+}
+
+/// Return the type of the list items, or `None` if the data type is not a list
+pub(crate) fn list_item_type(data_type: &DataType) -> Option<&DataType> {
+    match data_type {
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => Some(field.data_type()),
+        _ => None,
+    }
+}
+
+fn find_common_type(data_types: &[DataType]) -> Result<DataType> {
     let non_null_types = data_types
         .iter()
         .filter(|t| !t.is_null())
         .collect::<Vec<_>>();
 
     if non_null_types.is_empty() {
-        return Ok(&DataType::Null);
+        return Ok(DataType::Null);
+    }
+
+    if non_null_types.iter().any(|t| matches!(t, DataType::Dictionary(_, _))) {
+        return find_coerced_dictionary_type(&non_null_types);
+    }
+
+    if non_null_types.iter().all(|t| t.is_numeric()) {
+        return find_coerced_numeric_type(&non_null_types);
+    }
+
+    if non_null_types.iter().all(|t| matches!(t, DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64)) {
+        return find_coerced_temporal_type(&non_null_types);
     }
 
     let non_null_types_clone = non_null_types.clone();
@@ -19,10 +47,248 @@ pub(crate) fn find_coerced_type(data_types: &[DataType]) -> Result<&DataType> {
         let can_coerce_to_all = non_null_types.iter().all(|t| can_coerce_from(data_type, t));
 
         if can_coerce_to_all {
-            return Ok(data_type);
+            return Ok(data_type.clone());
+        }
+    }
+
+    plan_err!("none of the types can be coerced from all the others")
+}
+
+/// Create an error that names the arguments that can't be coerced to the type of most of the arguments,
+/// so calls with a lot of arguments are easy to fix
+fn incompatible_arguments_error(name: &str, data_types: &[DataType], error: DataFusionError) -> DataFusionError {
+    let mut message = format!(
+        "Cannot find a common type for arguments of {}, data types: {:?}, {}",
+        name,
+        data_types,
+        error.message()
+    );
+
+    let is_compatible = |lhs: &DataType, rhs: &DataType| find_common_type(&[lhs.clone(), rhs.clone()]).is_ok();
+
+    // The type that most of the arguments can be coerced together with, the first one wins ties
+    let majority_type = data_types
+        .iter()
+        .filter(|t| !t.is_null())
+        .rev()
+        .max_by_key(|majority_type| data_types.iter().filter(|t| is_compatible(majority_type, t)).count());
+
+    if let Some(majority_type) = majority_type {
+        let (conflicting, compatible): (Vec<_>, Vec<_>) = data_types
+            .iter()
+            .enumerate()
+            .partition(|(_, t)| !is_compatible(majority_type, t));
+
+        // Suggest the type of the compatible arguments, as the conflicting ones should be coerced to it
+        let target_type = find_common_type(&compatible.iter().map(|(_, t)| (*t).clone()).collect::<Vec<_>>())
+            .unwrap_or_else(|_| majority_type.clone());
+
+        if !conflicting.is_empty() {
+            let arguments = conflicting
+                .iter()
+                .enumerate()
+                .map(|(i, (index, t))| format!("{} {} ({})", if i == 0 { "Argument" } else { "argument" }, index + 1, t))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            message.push_str(&format!(
+                ". {} can't be coerced to {}, the type of most of the arguments, cast with `arrow_cast(<argument>, '{}')` or use arguments of compatible types",
+                arguments,
+                target_type,
+                target_type
+            ));
+        }
+    }
+
+    DataFusionError::Plan(message)
+}
+
+/// Dictionaries are coerced by their value types, with a key type that fits the keys of all the dictionaries
+/// and is at least 32 bits, as the values used by the result can come from all the dictionaries together
+///
+/// When some of the arguments are not dictionaries the result is the common value type,
+/// as the distinct values of a plain argument may not fit the keys of the dictionaries
+fn find_coerced_dictionary_type(non_null_types: &[&DataType]) -> Result<DataType> {
+    let (key_types, value_types): (Vec<_>, Vec<_>) = non_null_types
+        .iter()
+        .map(|t| match t {
+            DataType::Dictionary(key_type, value_type) => (Some(key_type.as_ref().clone()), value_type.as_ref().clone()),
+            t => (None, (*t).clone()),
+        })
+        .unzip();
+
+    let value_type = find_common_type(&value_types)?;
+
+    let Some(key_types) = key_types.into_iter().collect::<Option<Vec<_>>>() else {
+        return Ok(value_type);
+    };
+
+    match find_common_type(&key_types) {
+        Ok(key_type) if key_type.is_dictionary_key_type() => {
+            let key_type = match key_type {
+                DataType::Int8 | DataType::Int16 => DataType::Int32,
+                DataType::UInt8 | DataType::UInt16 => DataType::UInt32,
+                key_type => key_type,
+            };
+
+            Ok(DataType::Dictionary(Box::new(key_type), Box::new(value_type)))
         }
+
+        // The keys are only coerced to a type that can't be a key, for example UInt64 and Int64 are coerced to a decimal
+        _ => Ok(value_type),
+    }
+}
+
+fn is_decimal(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Decimal128(_, _) | DataType::Decimal256(_, _))
+}
+
+/// Numeric types are widened to the smallest type that can hold the values of all the arguments,
+/// similar to the common supertype of Spark and Postgres, so the result can be a type that is not one of the arguments:
+/// - Floating points win over integers and decimals, and decimals with floating points are `Float64`
+/// - Decimals with integers are widened decimals
+/// - Integers with the same signedness are the widest of them,
+///   and signed with unsigned integers are a signed integer that is wider than the unsigned ones,
+///   for example `Int8` and `UInt8` are `Int16`, while `Int64` and `UInt64` are `Decimal(20, 0)`
+fn find_coerced_numeric_type(non_null_types: &[&DataType]) -> Result<DataType> {
+    if non_null_types.iter().any(|t| t.is_floating()) {
+        return Ok(find_coerced_floating_type(non_null_types));
+    }
+
+    if non_null_types.iter().any(|t| is_decimal(t)) {
+        return find_coerced_decimal_type(non_null_types);
+    }
+
+    Ok(find_coerced_integer_type(non_null_types))
+}
+
+fn find_coerced_floating_type(non_null_types: &[&DataType]) -> DataType {
+    if non_null_types.iter().any(|t| is_decimal(t) || **t == DataType::Float64) {
+        DataType::Float64
+    } else if non_null_types.iter().all(|t| **t == DataType::Float16) {
+        DataType::Float16
+    } else {
+        DataType::Float32
+    }
+}
+
+fn find_coerced_integer_type(non_null_types: &[&DataType]) -> DataType {
+    let bits = |data_type: &DataType| match data_type {
+        DataType::Int8 | DataType::UInt8 => 8,
+        DataType::Int16 | DataType::UInt16 => 16,
+        DataType::Int32 | DataType::UInt32 => 32,
+        _ => 64,
+    };
+
+    let signed_bits = non_null_types.iter().filter(|t| t.is_signed_integer()).map(|t| bits(t)).max();
+    let unsigned_bits = non_null_types.iter().filter(|t| t.is_unsigned_integer()).map(|t| bits(t)).max();
+
+    match (signed_bits, unsigned_bits) {
+        (Some(signed_bits), Some(unsigned_bits)) => match signed_bits.max(unsigned_bits * 2) {
+            16 => DataType::Int16,
+            32 => DataType::Int32,
+            64 => DataType::Int64,
+
+            // No signed integer holds all the UInt64 values
+            _ => DataType::Decimal128(20, 0),
+        },
+        (Some(8), None) => DataType::Int8,
+        (Some(16), None) => DataType::Int16,
+        (Some(32), None) => DataType::Int32,
+        (Some(_), None) => DataType::Int64,
+        (None, Some(8)) => DataType::UInt8,
+        (None, Some(16)) => DataType::UInt16,
+        (None, Some(32)) => DataType::UInt32,
+        (None, _) => DataType::UInt64,
+    }
+}
+
+/// Decimals are widened so the values of all the arguments fit:
+/// the scale is the largest scale, and the number of digits before the decimal point is the largest of all the arguments.
+/// Integers are treated as decimals with enough digits for their values, for example `Int32` is `Decimal(10, 0)`.
+///
+/// The result is `Decimal128` when the precision fits, otherwise `Decimal256`
+fn find_coerced_decimal_type(non_null_types: &[&DataType]) -> Result<DataType> {
+    let mut scale = i8::MIN;
+    let mut integer_digits = 0;
+
+    for data_type in non_null_types {
+        let (type_precision, type_scale) = match data_type {
+            DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => (*precision, *scale),
+            DataType::Int8 | DataType::UInt8 => (3, 0),
+            DataType::Int16 | DataType::UInt16 => (5, 0),
+            DataType::Int32 | DataType::UInt32 => (10, 0),
+            DataType::Int64 => (19, 0),
+            DataType::UInt64 => (20, 0),
+            data_type => return plan_err!("{} is not a decimal or an integer", data_type),
+        };
+
+        scale = scale.max(type_scale);
+        integer_digits = integer_digits.max(type_precision as i16 - type_scale as i16);
+    }
+
+    let precision = integer_digits + scale as i16;
+
+    if precision <= DECIMAL128_MAX_PRECISION as i16 {
+        Ok(DataType::Decimal128(precision as u8, scale))
+    } else if precision <= DECIMAL256_MAX_PRECISION as i16 {
+        Ok(DataType::Decimal256(precision as u8, scale))
+    } else {
+        plan_err!(
+            "a decimal with all the values requires a precision of {} which is more than the maximum of {}",
+            precision,
+            DECIMAL256_MAX_PRECISION
+        )
+    }
+}
+
+/// Timestamps and dates are coerced so their instants are compared:
+/// - The time unit is the finest unit of all the timestamps, so no precision is lost
+/// - The time zone is kept when all the timestamps have the same one, otherwise it is UTC,
+///   as timestamps with a time zone store the instant in UTC and timestamps without one are read as UTC
+/// - Dates are midnight UTC, they can only be compared to timestamps without a time zone,
+///   as casting a date to a timestamp with a time zone is not supported.
+///   Only dates are coerced to `Date64` if any of them is `Date64`
+fn find_coerced_temporal_type(non_null_types: &[&DataType]) -> Result<DataType> {
+    let timestamps = non_null_types
+        .iter()
+        .filter_map(|t| match t {
+            DataType::Timestamp(unit, time_zone) => Some((unit, time_zone)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let Some(&(_, first_time_zone)) = timestamps.first() else {
+        return if non_null_types.contains(&&DataType::Date64) {
+            Ok(DataType::Date64)
+        } else {
+            Ok(DataType::Date32)
+        };
+    };
+
+    let unit = timestamps
+        .iter()
+        .map(|(unit, _)| *unit)
+        .max_by_key(|unit| match unit {
+            TimeUnit::Second => 0,
+            TimeUnit::Millisecond => 1,
+            TimeUnit::Microsecond => 2,
+            TimeUnit::Nanosecond => 3,
+        })
+        .cloned()
+        .unwrap_or(TimeUnit::Nanosecond);
+
+    let time_zone = if timestamps.iter().all(|(_, time_zone)| *time_zone == first_time_zone) {
+        first_time_zone.clone()
+    } else {
+        Some("UTC".into())
+    };
+
+    let has_dates = timestamps.len() < non_null_types.len();
+
+    if has_dates && time_zone.is_some() {
+        return plan_err!("dates can only be compared to timestamps without a time zone");
     }
 
-    // For better error messages, we can find the ones that are incompatible with the rest
-    plan_err!("Cannot find a common type for arguments, data types: {:?}", data_types)
+    Ok(DataType::Timestamp(unit, time_zone))
 }
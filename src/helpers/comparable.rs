@@ -0,0 +1,55 @@
+use datafusion::arrow::datatypes::{DataType, Field};
+use datafusion::error::Result;
+use datafusion_common::plan_err;
+
+/// Return an error if the values of the data type can't be ordered, naming the nested field that can't be ordered
+///
+/// The types that can be ordered are the same as the ones supported by
+/// [`make_comparator`](datafusion::arrow::array::make_comparator), so unsupported types fail when planning
+/// instead of when executing
+pub(crate) fn check_comparable(name: &str, data_type: &DataType) -> Result<()> {
+    match find_not_comparable(data_type) {
+        None => Ok(()),
+        Some((path, _)) if path.is_empty() => {
+            plan_err!("{} cannot compare arguments of type {} as it has no natural order", name, data_type)
+        }
+        Some((path, not_comparable)) => plan_err!(
+            "{} cannot compare arguments of type {} as the nested field `{}` of type {} has no natural order",
+            name,
+            data_type,
+            path.join("."),
+            not_comparable
+        ),
+    }
+}
+
+/// Return the path of field names to the first type that can't be ordered and that type
+///
+/// Null is only comparable at the top level, where the arguments are coerced to the type of the other arguments,
+/// nested Null (for example `List(Null)`) has no natural order
+fn find_not_comparable(data_type: &DataType) -> Option<(Vec<String>, DataType)> {
+    let find_in_field = |field: &Field| {
+        let not_comparable = match field.data_type() {
+            DataType::Null => Some((vec![], DataType::Null)),
+            data_type => find_not_comparable(data_type),
+        };
+
+        not_comparable.map(|(mut path, not_comparable)| {
+            path.insert(0, field.name().clone());
+            (path, not_comparable)
+        })
+    };
+
+    match data_type {
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => find_in_field(field),
+        DataType::Struct(fields) => fields.iter().find_map(|field| find_in_field(field)),
+        DataType::Dictionary(_, value_type) if value_type.is_null() => Some((vec![], data_type.clone())),
+        DataType::Dictionary(_, value_type) => find_not_comparable(value_type),
+        DataType::Map(_, _)
+        | DataType::Union(_, _)
+        | DataType::RunEndEncoded(_, _)
+        | DataType::ListView(_)
+        | DataType::LargeListView(_) => Some((vec![], data_type.clone())),
+        _ => None,
+    }
+}
@@ -1,13 +1,16 @@
-use datafusion::arrow::array::{make_comparator, Array, ArrayRef, BooleanArray};
-use datafusion::arrow::buffer::BooleanBuffer;
+use crate::helpers::{check_comparable, contains_float, find_coerced_type, nan_mask, normalize_nested_nan};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::array::{make_array, make_comparator, Array, ArrayRef, BooleanArray, Scalar};
+use datafusion::arrow::buffer::{BooleanBuffer, NullBuffer};
 use datafusion::arrow::compute::SortOptions;
 use datafusion::error::Result;
 
 use datafusion::arrow::compute::kernels::cmp;
 use datafusion::arrow::compute::kernels::zip::zip;
-use datafusion_common::ScalarValue;
+use datafusion_common::{exec_err, ScalarValue};
+use std::cmp::Ordering;
 
-const SORT_OPTIONS: SortOptions = SortOptions {
+pub(crate) const SORT_OPTIONS: SortOptions = SortOptions {
     // We want greatest first
     descending: false,
 
@@ -15,67 +18,369 @@ const SORT_OPTIONS: SortOptions = SortOptions {
     nulls_first: true,
 };
 
+/// Which of the two compared values should be kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum KeepOrder {
+    /// Keep the larger value, used by `greatest`
+    #[default]
+    Larger,
+
+    /// Keep the smaller value, used by `least`
+    Smaller,
+}
+
+impl KeepOrder {
+    /// Return true if the left value should be kept given the ordering of the left value compared to the right value
+    fn keep_lhs(&self, ordering: Ordering) -> bool {
+        match self {
+            KeepOrder::Larger => ordering.is_ge(),
+            KeepOrder::Smaller => ordering.is_le(),
+        }
+    }
+
+    /// Name of the function that keeps the values in this order, used in error messages
+    fn function_name(&self) -> &'static str {
+        match self {
+            KeepOrder::Larger => "greatest",
+            KeepOrder::Smaller => "least",
+        }
+    }
+}
+
+/// Options that control which value is kept when comparing two values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct CompareOptions {
+    pub(crate) order: KeepOrder,
+    pub(crate) null_handling: NullHandling,
+    pub(crate) nan_ordering: NanOrdering,
+}
+
+impl CompareOptions {
+    pub(crate) fn new(order: KeepOrder, null_handling: NullHandling, nan_ordering: NanOrdering) -> Self {
+        Self {
+            order,
+            null_handling,
+            nan_ordering,
+        }
+    }
+
+    /// Return true if the left value should be kept given whether each side is null
+    /// and a lazily computed decision for when both sides are not null
+    pub(crate) fn keep_lhs(&self, lhs_is_null: bool, rhs_is_null: bool, keep_lhs_not_null: impl FnOnce() -> bool) -> bool {
+        match self.null_handling {
+            // Keep the left value only if it is not null, or if both are null
+            NullHandling::Skip => !lhs_is_null && (rhs_is_null || keep_lhs_not_null()),
+
+            // Keep the left value if it is null so the null is propagated
+            NullHandling::Propagate => lhs_is_null || (!rhs_is_null && keep_lhs_not_null()),
+        }
+    }
+
+    /// Return true if the left value should be kept given whether each side is NaN
+    /// and a lazily computed ordering of the left value compared to the right value
+    ///
+    /// Both values must not be null
+    pub(crate) fn keep_lhs_not_null(&self, lhs_is_nan: bool, rhs_is_nan: bool, ordering: impl FnOnce() -> Ordering) -> bool {
+        if !lhs_is_nan && !rhs_is_nan {
+            return self.order.keep_lhs(ordering());
+        }
+
+        match self.nan_ordering {
+            // NaN is greater than any other value and all NaN values are equal
+            NanOrdering::Greatest => self.order.keep_lhs(lhs_is_nan.cmp(&rhs_is_nan)),
+
+            // Keep the left value only if it is not NaN, or if both are NaN
+            NanOrdering::Skip => !lhs_is_nan || rhs_is_nan,
+
+            // Keep the left value if it is NaN so the NaN is propagated
+            NanOrdering::Propagate => lhs_is_nan,
+        }
+    }
+}
 
 /// Return boolean array where `arr[i] = lhs[i] >= rhs[i]` for all i, where `arr` is the result array
-/// Nulls are always considered smaller than any other value
-pub(crate) fn get_larger(lhs: &dyn Array, rhs: &dyn Array) -> Result<BooleanArray> {
+/// (or `lhs[i] <= rhs[i]` when keeping the smaller value)
+///
+/// Nulls are either skipped or propagated depending on the null handling in `options`,
+/// and NaN values are ordered according to the NaN ordering in `options`
+///
+/// Both arrays must have the same length, unless one of them has a single row which is then compared to every row of the other
+pub(crate) fn get_larger(lhs: &dyn Array, rhs: &dyn Array, options: CompareOptions) -> Result<BooleanArray> {
+    match (lhs.len(), rhs.len()) {
+        (lhs_len, rhs_len) if lhs_len == rhs_len => {}
+
+        // Single row arrays are compared as a scalar against every row of the other array, without repeating them
+        (_, 1) => return get_larger_than_scalar(lhs, &ScalarValue::try_from_array(rhs, 0)?, options),
+        (1, _) => return get_scalar_larger_than(&ScalarValue::try_from_array(lhs, 0)?, rhs, options),
+
+        (lhs_len, rhs_len) => return exec_err!("Cannot compare arrays with different lengths, {} and {} rows", lhs_len, rhs_len),
+    }
+
+    // All the values are null so there is nothing to compare, the Null type has no order
+    if lhs.data_type().is_null() && rhs.data_type().is_null() {
+        return Ok(keep_lhs_all_null(lhs.len(), options));
+    }
+
+    // NaN bitmaps, only available for floating point arrays
+    let lhs_nan = nan_mask(lhs).filter(|nan| nan.count_set_bits() > 0);
+    let rhs_nan = nan_mask(rhs).filter(|nan| nan.count_set_bits() > 0);
+
     // Fast path:
-    // If both arrays are not nested, have the same length and no nulls, we can use the faster vectorised kernel
+    // If both arrays are not nested, have the same length and no NaN, we can use the faster vectorised kernel
     // - If both arrays are not nested: Nested types, such as lists, are not supported as the null semantics are not well-defined.
-    // - both array does not have any nulls: cmp::gt_eq will return null if any of the input is null while we want to return false in that case
-    if !lhs.data_type().is_nested() && lhs.null_count() == 0 && rhs.null_count() == 0 {
-        return cmp::gt_eq(&lhs, &rhs).map_err(|e| e.into());
+    // - both array does not have any NaN: cmp::gt_eq use total order where negative NaN is smaller than any other value
+    if !lhs.data_type().is_nested() && !lhs.data_type().is_null() && lhs_nan.is_none() && rhs_nan.is_none() {
+        let result = match options.order {
+            KeepOrder::Larger => cmp::gt_eq(&lhs, &rhs),
+            KeepOrder::Smaller => cmp::lt_eq(&lhs, &rhs),
+        }?;
+
+        // cmp::gt_eq will return null if any of the input is null while we want to return true or false in that case
+        let values = keep_lhs_nullable(result.values(), lhs.logical_nulls().as_ref(), rhs.logical_nulls().as_ref(), options);
+
+        return Ok(BooleanArray::new(values, None));
     }
 
-    let cmp = make_comparator(lhs, rhs, SORT_OPTIONS)?;
+    // NaN items inside nested values should be ordered the same way as top level NaN values
+    let lhs_normalized = normalize_nested_nan(&make_array(lhs.to_data()), options.nan_ordering)?;
+    let rhs_normalized = normalize_nested_nan(&make_array(rhs.to_data()), options.nan_ordering)?;
 
-    // We should have both arrays of the same length
-    let len = lhs.len().min(rhs.len());
+    // The comparator always order nulls first so nested values (e.g. list items) have the same order
+    // for both greatest and least, top level nulls are handled below
+    let cmp = make_comparator(lhs_normalized.as_ref(), rhs_normalized.as_ref(), SORT_OPTIONS)?;
+
+    let lhs_nulls = lhs.logical_nulls();
+    let rhs_nulls = rhs.logical_nulls();
 
     // Faster than using creating iterator
     // Why not iterator: https://github.com/apache/arrow-rs/pull/6395
-    let values = BooleanBuffer::collect_bool(len, |i| cmp(i, i).is_ge());
+    let values = BooleanBuffer::collect_bool(lhs.len(), |i| {
+        options.keep_lhs(
+            lhs_nulls.as_ref().is_some_and(|nulls| nulls.is_null(i)),
+            rhs_nulls.as_ref().is_some_and(|nulls| nulls.is_null(i)),
+            || options.keep_lhs_not_null(
+                lhs_nan.as_ref().is_some_and(|nan| nan.value(i)),
+                rhs_nan.as_ref().is_some_and(|nan| nan.value(i)),
+                || cmp(i, i),
+            ),
+        )
+    });
 
     // No nulls as we only want to keep the values that are larger, its either true or false
     Ok(BooleanArray::new(values, None))
 }
 
-/// Return array where the largest value at each index is kept
-pub(crate) fn keep_larger(lhs: ArrayRef, rhs: ArrayRef) -> Result<ArrayRef> {
+/// Return which values to keep from the left side when all the values on both sides are null
+fn keep_lhs_all_null(len: usize, options: CompareOptions) -> BooleanArray {
+    let keep_lhs = options.keep_lhs(true, true, || true);
+
+    BooleanArray::new(BooleanBuffer::collect_bool(len, |_| keep_lhs), None)
+}
+
+/// Return which values to keep from the left side given the comparison of the values regardless of nulls
+/// and the validity of each side, `None` validity means all values are valid
+///
+/// This is the same as [`CompareOptions::keep_lhs`] for all the rows at once
+fn keep_lhs_nullable(compared: &BooleanBuffer, lhs_nulls: Option<&NullBuffer>, rhs_nulls: Option<&NullBuffer>, options: CompareOptions) -> BooleanBuffer {
+    match options.null_handling {
+        // lhs is valid and (rhs is null or lhs is larger)
+        NullHandling::Skip => {
+            let keep = match rhs_nulls {
+                Some(rhs_nulls) => &!rhs_nulls.inner() | compared,
+                None => compared.clone(),
+            };
+
+            match lhs_nulls {
+                Some(lhs_nulls) => lhs_nulls.inner() & &keep,
+                None => keep,
+            }
+        }
+
+        // lhs is null or (rhs is valid and lhs is larger)
+        NullHandling::Propagate => {
+            let keep = match rhs_nulls {
+                Some(rhs_nulls) => rhs_nulls.inner() & compared,
+                None => compared.clone(),
+            };
+
+            match lhs_nulls {
+                Some(lhs_nulls) => &!lhs_nulls.inner() | &keep,
+                None => keep,
+            }
+        }
+    }
+}
+
+/// Return array where the largest (or smallest, depending on `options`) value at each index is kept
+pub(crate) fn keep_larger(lhs: ArrayRef, rhs: ArrayRef, options: CompareOptions) -> Result<ArrayRef> {
+    // True for values that we should keep from the left array
+    let keep_lhs = get_larger(lhs.as_ref(), rhs.as_ref(), options)?;
+
+    // Single row arrays are broadcast the same way as in `get_larger`
+    let larger = if lhs.len() == 1 && rhs.len() != 1 {
+        zip(&keep_lhs, &Scalar::new(lhs), &rhs)?
+    } else if rhs.len() == 1 && lhs.len() != 1 {
+        zip(&keep_lhs, &lhs, &Scalar::new(rhs))?
+    } else {
+        zip(&keep_lhs, &lhs, &rhs)?
+    };
+
+    Ok(larger)
+}
+
+/// Return boolean array where `arr[i] = lhs[i] >= rhs` for all i, where `arr` is the result array
+/// (or `lhs[i] <= rhs` when keeping the smaller value)
+///
+/// This is the same as [`get_larger`] with the scalar repeated for every row, without materializing it
+pub(crate) fn get_larger_than_scalar(lhs: &dyn Array, rhs: &ScalarValue, options: CompareOptions) -> Result<BooleanArray> {
+    get_larger_with_scalar(lhs, rhs, false, options)
+}
+
+/// Return boolean array where `arr[i] = lhs >= rhs[i]` for all i, where `arr` is the result array
+/// (or `lhs <= rhs[i]` when keeping the smaller value)
+///
+/// This is the same as [`get_larger_than_scalar`] with the scalar on the left side
+pub(crate) fn get_scalar_larger_than(lhs: &ScalarValue, rhs: &dyn Array, options: CompareOptions) -> Result<BooleanArray> {
+    get_larger_with_scalar(rhs, lhs, true, options)
+}
+
+/// Compare each row of the array to the scalar, where the scalar is on the left side when `scalar_is_lhs` is true
+fn get_larger_with_scalar(array: &dyn Array, scalar: &ScalarValue, scalar_is_lhs: bool, options: CompareOptions) -> Result<BooleanArray> {
+    let scalar_is_null = scalar.is_null();
+
+    // Single row array, so the scalar value is never repeated
+    let scalar = scalar.to_array()?;
+
+    // All the values are null so there is nothing to compare, the same as in `get_larger`
+    if array.data_type().is_null() && scalar.data_type().is_null() {
+        return Ok(keep_lhs_all_null(array.len(), options));
+    }
+
+    let array_nan = nan_mask(array).filter(|nan| nan.count_set_bits() > 0);
+    let scalar_is_nan = nan_mask(scalar.as_ref()).is_some_and(|nan| nan.value(0));
+
+    let array_nulls = array.logical_nulls();
+
+    // Fast path: same as in `get_larger`, comparing to the scalar using the vectorised kernel
+    if !array.data_type().is_nested() && !array.data_type().is_null() && !scalar_is_null && array_nan.is_none() && !scalar_is_nan {
+        let scalar = Scalar::new(scalar);
+
+        let result = match (options.order, scalar_is_lhs) {
+            (KeepOrder::Larger, false) => cmp::gt_eq(&array, &scalar),
+            (KeepOrder::Smaller, false) => cmp::lt_eq(&array, &scalar),
+            (KeepOrder::Larger, true) => cmp::gt_eq(&scalar, &array),
+            (KeepOrder::Smaller, true) => cmp::lt_eq(&scalar, &array),
+        }?;
+
+        let values = match scalar_is_lhs {
+            false => keep_lhs_nullable(result.values(), array_nulls.as_ref(), None, options),
+            true => keep_lhs_nullable(result.values(), None, array_nulls.as_ref(), options),
+        };
+
+        return Ok(BooleanArray::new(values, None));
+    }
+
+    // NaN items inside nested values should be ordered the same way as top level NaN values
+    let array_normalized = normalize_nested_nan(&make_array(array.to_data()), options.nan_ordering)?;
+    let scalar_normalized = normalize_nested_nan(&scalar, options.nan_ordering)?;
+
+    // Compare each row to the single row of the scalar
+    let cmp = make_comparator(array_normalized.as_ref(), scalar_normalized.as_ref(), SORT_OPTIONS)?;
+
+    let values = BooleanBuffer::collect_bool(array.len(), |i| {
+        let row_is_null = array_nulls.as_ref().is_some_and(|nulls| nulls.is_null(i));
+        let row_is_nan = array_nan.as_ref().is_some_and(|nan| nan.value(i));
+
+        match scalar_is_lhs {
+            false => options.keep_lhs(row_is_null, scalar_is_null, || {
+                options.keep_lhs_not_null(row_is_nan, scalar_is_nan, || cmp(i, 0))
+            }),
+            true => options.keep_lhs(scalar_is_null, row_is_null, || {
+                options.keep_lhs_not_null(scalar_is_nan, row_is_nan, || cmp(i, 0).reverse())
+            }),
+        }
+    });
+
+    Ok(BooleanArray::new(values, None))
+}
+
+/// Return array where the largest (or smallest, depending on `options`) value between each row and the scalar is kept
+///
+/// The scalar is only copied into the rows where it is kept
+pub(crate) fn keep_larger_than_scalar(lhs: ArrayRef, rhs: &ScalarValue, options: CompareOptions) -> Result<ArrayRef> {
     // True for values that we should keep from the left array
-    let keep_lhs = get_larger(lhs.as_ref(), rhs.as_ref())?;
+    let keep_lhs = get_larger_than_scalar(lhs.as_ref(), rhs, options)?;
 
-    let larger = zip(&keep_lhs, &lhs, &rhs)?;
+    let larger = zip(&keep_lhs, &lhs, &rhs.to_scalar()?)?;
 
     Ok(larger)
 }
 
-/// Return the larger scalar value
+/// Return true if the left scalar is larger or equal than the right scalar
+/// (or smaller or equal when keeping the smaller value)
 ///
-/// Return true if the value from the left scalar is larger or equal than the value from the right scalar
-/// Return false if the value from the right scalar is larger than the value from the left scalar
-pub(crate) fn keep_larger_scalar<'a>(lhs: &'a ScalarValue, rhs: &'a ScalarValue) -> Result<&'a ScalarValue> {
-
-    // If not complex type we can use the fast path
-    if !lhs.data_type().is_nested() {
-        return if lhs >= rhs {
-            Ok(lhs)
-        } else {
-            Ok(rhs)
+/// Nulls are either skipped or propagated depending on the null handling in `options`,
+/// and NaN values are ordered according to the NaN ordering in `options`
+pub(crate) fn get_larger_scalar(lhs: &ScalarValue, rhs: &ScalarValue, options: CompareOptions) -> Result<bool> {
+    // Scalars of different types (including an untyped NULL) are compared as their common type
+    if lhs.data_type() != rhs.data_type() {
+        let (lhs, rhs) = coerce_scalars(lhs, rhs, options)?;
+
+        return get_larger_scalar(&lhs, &rhs, options);
+    }
+
+    let lhs_is_null = lhs.is_null();
+    let rhs_is_null = rhs.is_null();
+
+    if lhs_is_null || rhs_is_null {
+        return Ok(options.keep_lhs(lhs_is_null, rhs_is_null, || true));
+    }
+
+    // If not complex type and not floating point (including dictionary of floats) we can use the fast path
+    if !lhs.data_type().is_nested() && !contains_float(&lhs.data_type()) {
+        return match lhs.partial_cmp(rhs) {
+            Some(ordering) => Ok(options.order.keep_lhs(ordering)),
+            None => exec_err!("Cannot compare {} to {}", lhs.data_type(), rhs.data_type()),
         };
     }
 
-    // If complex type we can't compare directly as we want null values to be smaller
-    let cmp = make_comparator(
+    // If complex type or floating point we compare them as single row arrays
+    // so nested nulls and NaN values are ordered exactly the same as in the arrays path
+    let keep_lhs = get_larger(
         lhs.to_array()?.as_ref(),
         rhs.to_array()?.as_ref(),
-        SORT_OPTIONS,
+        options,
     )?;
 
-    if cmp(0, 0).is_ge() {
-        Ok(lhs)
+    Ok(keep_lhs.value(0))
+}
+
+/// Cast both scalars to their common type, or return an error if they can't be compared
+fn coerce_scalars(lhs: &ScalarValue, rhs: &ScalarValue, options: CompareOptions) -> Result<(ScalarValue, ScalarValue)> {
+    let data_type = find_coerced_type(options.order.function_name(), &[lhs.data_type(), rhs.data_type()])?;
+
+    check_comparable(options.order.function_name(), &data_type)?;
+
+    Ok((lhs.cast_to(&data_type)?, rhs.cast_to(&data_type)?))
+}
+
+/// Return the larger scalar value (or the smaller one, depending on `options`)
+///
+/// Return the left scalar if it is larger or equal than the right scalar
+/// Return the right scalar if it is larger than the left scalar
+///
+/// Scalars of different types are cast to their common type, so the returned value has that type
+pub(crate) fn keep_larger_scalar(lhs: &ScalarValue, rhs: &ScalarValue, options: CompareOptions) -> Result<ScalarValue> {
+    if lhs.data_type() != rhs.data_type() {
+        let (lhs, rhs) = coerce_scalars(lhs, rhs, options)?;
+
+        return keep_larger_scalar(&lhs, &rhs, options);
+    }
+
+    if get_larger_scalar(lhs, rhs, options)? {
+        Ok(lhs.clone())
     } else {
-        Ok(rhs)
+        Ok(rhs.clone())
     }
 }
 
@@ -84,10 +389,17 @@ mod tests {
     use super::*;
     use crate::tests::utils::create_primitive_array;
     use crate::*;
-    use datafusion::arrow::array::{ArrowPrimitiveType, AsArray, ListArray};
-    use datafusion::arrow::datatypes::{Int32Type, Int8Type};
+    use datafusion::arrow::array::{ArrowPrimitiveType, AsArray, Float64Array, Int32Array, ListArray, NullArray, StringArray};
+    use datafusion::arrow::datatypes::{DataType, Int32Type, Int8Type};
+    use rand::Rng;
     use std::sync::Arc;
 
+    const SMALLER: CompareOptions = CompareOptions {
+        order: KeepOrder::Smaller,
+        null_handling: NullHandling::Skip,
+        nan_ordering: NanOrdering::Greatest,
+    };
+
     fn keep_larger_for_regular_vecs<T: Ord + Copy>(vectors: Vec<Vec<Option<T>>>) -> Vec<Option<T>> {
         vectors
             .transpose()
@@ -108,6 +420,26 @@ mod tests {
             .collect()
     }
 
+    fn keep_smaller_for_regular_vecs<T: Ord + Copy>(vectors: Vec<Vec<Option<T>>>) -> Vec<Option<T>> {
+        vectors
+            .transpose()
+            .iter()
+            .map(|v| {
+                let mut min = v[0];
+                for x in v {
+                    if min.is_none() {
+                        min = *x;
+                    } else if x.is_none() {
+                        continue;
+                    } else if x < &min {
+                        min = *x;
+                    }
+                }
+                min
+            })
+            .collect()
+    }
+
     fn create_scalar_list<T, P, I>(iter: I) -> ScalarValue
     where
         T: ArrowPrimitiveType,
@@ -126,7 +458,7 @@ mod tests {
         let array1 = create_primitive_array::<Int8Type>(vec1.clone());
         let array2 = create_primitive_array::<Int8Type>(vec2.clone());
 
-        let result = keep_larger(array1, array2).unwrap();
+        let result = keep_larger(array1, array2, CompareOptions::default()).unwrap();
 
         assert_eq!(result.as_primitive::<Int8Type>().clone(), *create_primitive_array::<Int8Type>(expected).as_ref());
     }
@@ -140,7 +472,7 @@ mod tests {
         let array1 = create_primitive_array::<Int8Type>(vec1.clone());
         let array2 = create_primitive_array::<Int8Type>(vec2.clone());
 
-        let result = keep_larger(array1, array2).unwrap();
+        let result = keep_larger(array1, array2, CompareOptions::default()).unwrap();
 
         assert_eq!(result.as_primitive::<Int8Type>().clone(), *create_primitive_array::<Int8Type>(expected).as_ref());
     }
@@ -151,76 +483,482 @@ mod tests {
             keep_larger_scalar(
                 &ScalarValue::Int32(Some(1)),
                 &ScalarValue::Int32(Some(2)),
+                CompareOptions::default(),
             ).unwrap(),
-            &ScalarValue::Int32(Some(2))
+            ScalarValue::Int32(Some(2))
         );
         assert_eq!(
             keep_larger_scalar(
                 &ScalarValue::Int32(Some(2)),
                 &ScalarValue::Int32(Some(1)),
+                CompareOptions::default(),
             ).unwrap(),
-            &ScalarValue::Int32(Some(2))
+            ScalarValue::Int32(Some(2))
         );
         assert_eq!(
             keep_larger_scalar(
                 &ScalarValue::Int32(Some(2)),
                 &ScalarValue::Int32(Some(2)),
+                CompareOptions::default(),
             ).unwrap(),
-            &ScalarValue::Int32(Some(2))
+            ScalarValue::Int32(Some(2))
         );
         assert_eq!(
             keep_larger_scalar(
                 &ScalarValue::Int32(None),
                 &ScalarValue::Int32(Some(2)),
+                CompareOptions::default(),
             ).unwrap(),
-            &ScalarValue::Int32(Some(2))
+            ScalarValue::Int32(Some(2))
         );
         assert_eq!(
             keep_larger_scalar(
                 &ScalarValue::Int32(Some(2)),
                 &ScalarValue::Int32(None),
+                CompareOptions::default(),
             ).unwrap(),
-            &ScalarValue::Int32(Some(2))
+            ScalarValue::Int32(Some(2))
         );
 
         assert_eq!(
             keep_larger_scalar(
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                CompareOptions::default(),
             ).unwrap(),
-            &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
         );
 
         assert_eq!(
             keep_larger_scalar(
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                CompareOptions::default(),
             ).unwrap(),
-            &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
         );
 
         assert_eq!(
             keep_larger_scalar(
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                CompareOptions::default(),
             ).unwrap(),
-                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
         );
 
         assert_eq!(
             keep_larger_scalar(
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
                 &create_scalar_list::<Int32Type, _, _>(vec![None::<Vec<Option<i32>>>]),
+                CompareOptions::default(),
             ).unwrap(),
-                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
+                create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
         );
 
         assert_eq!(
             keep_larger_scalar(
                 &create_scalar_list::<Int32Type, _, _>(vec![None::<Vec<Option<i32>>>]),
                 &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
+                CompareOptions::default(),
             ).unwrap(),
-                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
+                create_scalar_list::<Int32Type, _, _>(vec![Some(vec![])]),
+        );
+    }
+
+    #[test]
+    fn should_return_smaller_when_primitive_with_nulls() {
+        let vec1 = vec![Some(-1), None, Some(3), Some(0), None, None, Some(7), Some(8), Some(9), Some(10)];
+        let vec2 = vec![Some(5), None, Some(-2), None, Some(4), Some(20), None, Some(8), Some(12), Some(-3)];
+        let expected = keep_smaller_for_regular_vecs(vec![vec1.clone(), vec2.clone()]);
+
+        let array1 = create_primitive_array::<Int8Type>(vec1.clone());
+        let array2 = create_primitive_array::<Int8Type>(vec2.clone());
+
+        let result = keep_larger(array1, array2, SMALLER).unwrap();
+
+        assert_eq!(result.as_primitive::<Int8Type>().clone(), *create_primitive_array::<Int8Type>(expected).as_ref());
+    }
+
+    #[test]
+    fn should_return_smaller_when_primitives_without_nulls() {
+        let vec1 = vec![Some(-1), Some(9), Some(3), Some(0), Some(11), Some(-120), Some(7), Some(8), Some(9), Some(10)];
+        let vec2 = vec![Some(5), Some(13), Some(-2), Some(3), Some(4), Some(20), Some(83), Some(8), Some(12), Some(-3)];
+        let expected = keep_smaller_for_regular_vecs(vec![vec1.clone(), vec2.clone()]);
+
+        let array1 = create_primitive_array::<Int8Type>(vec1.clone());
+        let array2 = create_primitive_array::<Int8Type>(vec2.clone());
+
+        let result = keep_larger(array1, array2, SMALLER).unwrap();
+
+        assert_eq!(result.as_primitive::<Int8Type>().clone(), *create_primitive_array::<Int8Type>(expected).as_ref());
+    }
+
+    #[test]
+    fn keep_smaller_scalar_test() {
+        assert_eq!(
+            keep_larger_scalar(
+                &ScalarValue::Int32(Some(1)),
+                &ScalarValue::Int32(Some(2)),
+                SMALLER,
+            ).unwrap(),
+            ScalarValue::Int32(Some(1))
+        );
+        assert_eq!(
+            keep_larger_scalar(
+                &ScalarValue::Int32(Some(2)),
+                &ScalarValue::Int32(Some(1)),
+                SMALLER,
+            ).unwrap(),
+            ScalarValue::Int32(Some(1))
+        );
+        assert_eq!(
+            keep_larger_scalar(
+                &ScalarValue::Int32(None),
+                &ScalarValue::Int32(Some(2)),
+                SMALLER,
+            ).unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+        assert_eq!(
+            keep_larger_scalar(
+                &ScalarValue::Int32(Some(2)),
+                &ScalarValue::Int32(None),
+                SMALLER,
+            ).unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+
+        // Null list item is smaller than any value
+        assert_eq!(
+            keep_larger_scalar(
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                SMALLER,
+            ).unwrap(),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+        );
+
+        assert_eq!(
+            keep_larger_scalar(
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                SMALLER,
+            ).unwrap(),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+        );
+
+        // Null list is skipped
+        assert_eq!(
+            keep_larger_scalar(
+                &create_scalar_list::<Int32Type, _, _>(vec![None::<Vec<Option<i32>>>]),
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                SMALLER,
+            ).unwrap(),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+        );
+    }
+
+    #[test]
+    fn should_propagate_nulls_when_configured() {
+        let vec1 = vec![Some(-1), None, Some(3), Some(0), None, None, Some(7)];
+        let vec2 = vec![Some(5), None, Some(-2), None, Some(4), Some(20), None];
+        let expected = vec![Some(5), None, Some(3), None, None, None, None];
+
+        let array1 = create_primitive_array::<Int8Type>(vec1.clone());
+        let array2 = create_primitive_array::<Int8Type>(vec2.clone());
+
+        let result = keep_larger(array1, array2, CompareOptions::new(KeepOrder::Larger, NullHandling::Propagate, NanOrdering::Greatest)).unwrap();
+
+        assert_eq!(result.as_primitive::<Int8Type>().clone(), *create_primitive_array::<Int8Type>(expected).as_ref());
+    }
+
+    #[test]
+    fn keep_larger_scalar_propagate_nulls_test() {
+        let options = CompareOptions::new(KeepOrder::Larger, NullHandling::Propagate, NanOrdering::Greatest);
+
+        assert_eq!(
+            keep_larger_scalar(&ScalarValue::Int32(None), &ScalarValue::Int32(Some(2)), options).unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            keep_larger_scalar(&ScalarValue::Int32(Some(2)), &ScalarValue::Int32(None), options).unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            keep_larger_scalar(&ScalarValue::Int32(Some(1)), &ScalarValue::Int32(Some(2)), options).unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+
+        // Null list is propagated
+        assert_eq!(
+            keep_larger_scalar(
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                &create_scalar_list::<Int32Type, _, _>(vec![None::<Vec<Option<i32>>>]),
+                options,
+            ).unwrap(),
+            create_scalar_list::<Int32Type, _, _>(vec![None::<Vec<Option<i32>>>]),
         );
+
+        // Null list items are not propagated, only top level nulls are
+        assert_eq!(
+            keep_larger_scalar(
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])]),
+                &create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+                options,
+            ).unwrap(),
+            create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1)])]),
+        );
+    }
+
+    #[test]
+    fn keep_larger_than_scalar_same_as_repeated_scalar() {
+        let lists: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![None, Some(5)]),
+            Some(vec![Some(3)]),
+            Some(vec![]),
+        ]));
+        let floats: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.0), None, Some(f64::NAN), Some(-f64::NAN), Some(f64::INFINITY),
+        ]));
+        let strings: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("a"), Some("c"), None, Some("b"), Some("bb"),
+        ]));
+
+        let cases = vec![
+            (lists.clone(), create_scalar_list::<Int32Type, _, _>(vec![Some(vec![Some(1), Some(3)])])),
+            (lists.clone(), create_scalar_list::<Int32Type, _, _>(vec![Some(vec![None])])),
+            (lists, ScalarValue::try_from(&DataType::new_list(DataType::Int32, true)).unwrap()),
+            (floats.clone(), ScalarValue::Float64(Some(1.0))),
+            (floats.clone(), ScalarValue::Float64(Some(f64::NAN))),
+            (floats, ScalarValue::Float64(None)),
+            (strings.clone(), ScalarValue::Utf8(Some("b".to_string()))),
+            (strings, ScalarValue::Utf8(None)),
+        ];
+
+        for (array, scalar) in cases {
+            for order in [KeepOrder::Larger, KeepOrder::Smaller] {
+                for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+                    for nan_ordering in [NanOrdering::Greatest, NanOrdering::Skip, NanOrdering::Propagate] {
+                        let options = CompareOptions::new(order, null_handling, nan_ordering);
+
+                        let expected = keep_larger(array.clone(), scalar.to_array_of_size(array.len()).unwrap(), options).unwrap();
+                        let actual = keep_larger_than_scalar(array.clone(), &scalar, options).unwrap();
+
+                        // Compare the debug output, as NaN is not equal to itself
+                        assert_eq!(format!("{:?}", actual), format!("{:?}", expected), "{:?} with {:?}", scalar, options);
+
+                        // The scalar on the left side
+                        let expected = get_larger(scalar.to_array_of_size(array.len()).unwrap().as_ref(), array.as_ref(), options).unwrap();
+                        let actual = get_scalar_larger_than(&scalar, array.as_ref(), options).unwrap();
+
+                        assert_eq!(actual, expected, "{:?} with {:?}", scalar, options);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_larger_with_nulls_same_as_comparing_each_row() {
+        let lhs = Int32Array::from(vec![Some(1), None, Some(3), None, Some(5), Some(2)]);
+        let rhs = Int32Array::from(vec![Some(2), Some(2), None, None, Some(5), Some(1)]);
+        let no_nulls = Int32Array::from(vec![3, 1, 3, 0, 6, 2]);
+
+        let cases = [(&lhs, &rhs), (&rhs, &lhs), (&lhs, &no_nulls), (&no_nulls, &rhs)];
+
+        for (lhs, rhs) in cases {
+            for order in [KeepOrder::Larger, KeepOrder::Smaller] {
+                for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+                    let options = CompareOptions::new(order, null_handling, NanOrdering::Greatest);
+
+                    let expected = lhs
+                        .iter()
+                        .zip(rhs.iter())
+                        .map(|(l, r)| Some(options.keep_lhs(l.is_none(), r.is_none(), || match order {
+                            KeepOrder::Larger => l >= r,
+                            KeepOrder::Smaller => l <= r,
+                        })))
+                        .collect::<BooleanArray>();
+
+                    let actual = get_larger(lhs, rhs, options).unwrap();
+
+                    assert_eq!(actual, expected, "{:?} and {:?} with {:?}", lhs, rhs, options);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_larger_with_different_lengths() {
+        let cases: Vec<(ArrayRef, ArrayRef)> = vec![
+            (Arc::new(Int32Array::from(vec![1, 2, 3])), Arc::new(Int32Array::from(vec![1, 2]))),
+            (Arc::new(Float64Array::from(vec![f64::NAN, 2.0])), Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0]))),
+            (Arc::new(StringArray::from(vec!["a", "b"])), Arc::new(StringArray::from(Vec::<&str>::new()))),
+            (
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)]), None])),
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)]), None, Some(vec![])])),
+            ),
+        ];
+
+        for (lhs, rhs) in cases {
+            for options in [SMALLER, CompareOptions::new(KeepOrder::Larger, NullHandling::Propagate, NanOrdering::Skip)] {
+                let error = get_larger(lhs.as_ref(), rhs.as_ref(), options).unwrap_err();
+                assert!(error.message().contains("Cannot compare arrays with different lengths"), "{:?} and {:?}: {}", lhs, rhs, error);
+
+                assert!(keep_larger(lhs.clone(), rhs.clone(), options).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn single_row_arrays_are_broadcast() {
+        let arrays: Vec<(ArrayRef, ArrayRef)> = vec![
+            (Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(2)])), Arc::new(Int32Array::from(vec![Some(2)]))),
+            (Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(2)])), Arc::new(Int32Array::from(vec![None]))),
+            (Arc::new(Float64Array::from(vec![Some(1.0), Some(f64::NAN), None])), Arc::new(Float64Array::from(vec![Some(f64::NAN)]))),
+            (Arc::new(StringArray::from(vec![Some("a"), Some("c"), None])), Arc::new(StringArray::from(vec![Some("b")]))),
+            (
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)]), None, Some(vec![Some(3), None])])),
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(2)])])),
+            ),
+        ];
+
+        for (array, single_row) in arrays {
+            let repeated = ScalarValue::try_from_array(&single_row, 0).unwrap().to_array_of_size(array.len()).unwrap();
+
+            for order in [KeepOrder::Larger, KeepOrder::Smaller] {
+                for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+                    for nan_ordering in [NanOrdering::Greatest, NanOrdering::Skip, NanOrdering::Propagate] {
+                        let options = CompareOptions::new(order, null_handling, nan_ordering);
+
+                        // Single row on the right
+                        assert_eq!(
+                            get_larger(array.as_ref(), single_row.as_ref(), options).unwrap(),
+                            get_larger(array.as_ref(), repeated.as_ref(), options).unwrap(),
+                            "{:?} and {:?} with {:?}", array, single_row, options
+                        );
+                        assert_eq!(
+                            format!("{:?}", keep_larger(array.clone(), single_row.clone(), options).unwrap()),
+                            format!("{:?}", keep_larger(array.clone(), repeated.clone(), options).unwrap()),
+                        );
+
+                        // Single row on the left
+                        assert_eq!(
+                            get_larger(single_row.as_ref(), array.as_ref(), options).unwrap(),
+                            get_larger(repeated.as_ref(), array.as_ref(), options).unwrap(),
+                            "{:?} and {:?} with {:?}", single_row, array, options
+                        );
+                        assert_eq!(
+                            format!("{:?}", keep_larger(single_row.clone(), array.clone(), options).unwrap()),
+                            format!("{:?}", keep_larger(repeated.clone(), array.clone(), options).unwrap()),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keep_larger_scalar_of_different_types() {
+        let options = CompareOptions::default();
+
+        assert_eq!(keep_larger_scalar(&ScalarValue::Int8(Some(5)), &ScalarValue::Int32(Some(3)), options).unwrap(), ScalarValue::Int32(Some(5)));
+        assert_eq!(keep_larger_scalar(&ScalarValue::Int32(Some(3)), &ScalarValue::Int8(Some(5)), options).unwrap(), ScalarValue::Int32(Some(5)));
+        assert_eq!(keep_larger_scalar(&ScalarValue::Int8(Some(-1)), &ScalarValue::UInt8(Some(200)), options).unwrap(), ScalarValue::Int16(Some(200)));
+        assert_eq!(keep_larger_scalar(&ScalarValue::Int64(Some(1)), &ScalarValue::Float64(Some(0.5)), SMALLER).unwrap(), ScalarValue::Float64(Some(0.5)));
+
+        assert!(get_larger_scalar(&ScalarValue::Int8(Some(5)), &ScalarValue::Int32(Some(3)), options).unwrap());
+        assert!(!get_larger_scalar(&ScalarValue::Int8(Some(5)), &ScalarValue::Int32(Some(300)), options).unwrap());
+    }
+
+    #[test]
+    fn keep_larger_scalar_with_untyped_null() {
+        let skip = CompareOptions::default();
+        let propagate = CompareOptions::new(KeepOrder::Larger, NullHandling::Propagate, NanOrdering::Greatest);
+
+        for (lhs, rhs) in [(ScalarValue::Int32(Some(5)), ScalarValue::Null), (ScalarValue::Null, ScalarValue::Int32(Some(5)))] {
+            // The result has the type of the typed value
+            assert_eq!(keep_larger_scalar(&lhs, &rhs, skip).unwrap(), ScalarValue::Int32(Some(5)));
+            assert_eq!(keep_larger_scalar(&lhs, &rhs, propagate).unwrap(), ScalarValue::Int32(None));
+        }
+    }
+
+    #[test]
+    fn keep_larger_scalar_of_incomparable_types() {
+        let cases = [
+            (ScalarValue::Int32(Some(1)), ScalarValue::Boolean(Some(true))),
+            (ScalarValue::Boolean(Some(true)), ScalarValue::Binary(Some(vec![1]))),
+        ];
+
+        for (lhs, rhs) in cases {
+            assert!(keep_larger_scalar(&lhs, &rhs, CompareOptions::default()).is_err(), "{:?} and {:?}", lhs, rhs);
+            assert!(get_larger_scalar(&rhs, &lhs, CompareOptions::default()).is_err(), "{:?} and {:?}", rhs, lhs);
+        }
+    }
+
+    #[test]
+    fn scalar_and_array_paths_agree() {
+        let mut rng = rand::thread_rng();
+
+        let mut random_scalar = || {
+            let value = rng.gen_range(-3i8..=3);
+            let is_null = rng.gen_bool(0.2);
+
+            match rng.gen_range(0..8) {
+                0 => ScalarValue::Int8(Some(value).filter(|_| !is_null)),
+                1 => ScalarValue::Int16(Some(value as i16 * 100).filter(|_| !is_null)),
+                2 => ScalarValue::Int32(Some(value as i32).filter(|_| !is_null)),
+                3 => ScalarValue::Int64(Some(value as i64 * 1000).filter(|_| !is_null)),
+                4 => ScalarValue::UInt8(Some(value.unsigned_abs()).filter(|_| !is_null)),
+                5 => ScalarValue::Float64(Some(if value == 3 { f64::NAN } else { value as f64 / 2.0 }).filter(|_| !is_null)),
+                6 => ScalarValue::Decimal128(Some(value as i128 * 25).filter(|_| !is_null), 5, 2),
+                _ => ScalarValue::Null,
+            }
+        };
+
+        for _ in 0..1000 {
+            let lhs = random_scalar();
+            let rhs = random_scalar();
+
+            let data_type = find_coerced_type("greatest", &[lhs.data_type(), rhs.data_type()]).unwrap();
+            let lhs_array = lhs.cast_to(&data_type).unwrap().to_array().unwrap();
+            let rhs_array = rhs.cast_to(&data_type).unwrap().to_array().unwrap();
+
+            for order in [KeepOrder::Larger, KeepOrder::Smaller] {
+                for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+                    for nan_ordering in [NanOrdering::Greatest, NanOrdering::Skip, NanOrdering::Propagate] {
+                        let options = CompareOptions::new(order, null_handling, nan_ordering);
+
+                        let scalar_result = keep_larger_scalar(&lhs, &rhs, options).unwrap();
+                        let array_result = keep_larger(lhs_array.clone(), rhs_array.clone(), options).unwrap();
+                        let array_result = ScalarValue::try_from_array(&array_result, 0).unwrap();
+
+                        // Compare the debug output, as NaN is not equal to itself
+                        assert_eq!(format!("{:?}", scalar_result), format!("{:?}", array_result), "{:?} and {:?} with {:?}", lhs, rhs, options);
+
+                        assert_eq!(
+                            get_larger_scalar(&lhs, &rhs, options).unwrap(),
+                            get_larger(lhs_array.as_ref(), rhs_array.as_ref(), options).unwrap().value(0),
+                            "{:?} and {:?} with {:?}", lhs, rhs, options
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compare_null_arrays() {
+        let nulls: ArrayRef = Arc::new(NullArray::new(3));
+        let single_null: ArrayRef = Arc::new(NullArray::new(1));
+
+        for options in [CompareOptions::default(), CompareOptions::new(KeepOrder::Larger, NullHandling::Propagate, NanOrdering::Greatest)] {
+            assert_eq!(keep_larger(nulls.clone(), nulls.clone(), options).unwrap().as_ref(), nulls.as_ref());
+            assert_eq!(keep_larger(nulls.clone(), single_null.clone(), options).unwrap().as_ref(), nulls.as_ref());
+            assert_eq!(keep_larger_than_scalar(nulls.clone(), &ScalarValue::Null, options).unwrap().as_ref(), nulls.as_ref());
+        }
     }
 }
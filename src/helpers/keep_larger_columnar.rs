@@ -0,0 +1,303 @@
+use crate::helpers::{get_larger, get_larger_scalar, get_larger_than_scalar, get_scalar_larger_than, keep_larger, keep_larger_dictionary, keep_larger_primitive, keep_larger_scalar, keep_larger_than_scalar, CompareOptions};
+use crate::NullHandling;
+use datafusion::arrow::array::{new_null_array, Array, ArrayRef, AsArray, UInt64Array};
+use datafusion::arrow::compute::{cast, interleave};
+use datafusion::arrow::compute::kernels::zip::zip;
+use datafusion::arrow::datatypes::{DataType, UInt64Type};
+use datafusion::error::Result;
+use datafusion_common::{exec_err, internal_err, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use std::sync::Arc;
+
+/// Make sure all the arguments were coerced to the same data type before comparing them
+fn check_same_data_type(args: &[ColumnarValue]) -> Result<()> {
+    let data_type = args[0].data_type();
+
+    if args.iter().any(|arg| arg.data_type() != data_type) {
+        let data_types = args.iter().map(|arg| arg.data_type()).collect::<Vec<_>>();
+
+        return exec_err!("Expected all arguments to have the same data type, got {data_types:?}");
+    }
+
+    Ok(())
+}
+
+/// Return the largest (or smallest, depending on `options`) value at each row across all the arguments
+///
+/// This is the shared implementation of `greatest` and `least`,
+/// which permits important special cases when one or more of the arguments
+/// are single values (constants). For example `greatest(a, 2)`
+pub(crate) fn keep_larger_columnar(args: &[ColumnarValue], options: CompareOptions) -> Result<ColumnarValue> {
+    // DataFusion has arranged for the correct inputs to be passed to this
+    // function, but we check again to make sure, as the function can also be invoked directly
+    if args.len() < 2 {
+        return exec_err!("Expected at least 2 arguments, got {}", args.len());
+    }
+
+    check_same_data_type(args)?;
+
+    // Split to scalars and arrays for later optimization
+    let mut scalars = vec![];
+    let mut arrays = vec![];
+
+    for arg in args {
+        match arg {
+            ColumnarValue::Scalar(scalar) => scalars.push(scalar),
+            ColumnarValue::Array(array) => arrays.push(array),
+        }
+    }
+
+    let mut arrays_iter = arrays.iter().copied();
+
+    let mut largest: ArrayRef;
+
+    // Optimization: merge all scalars into one to avoid recomputing
+    if let Some((&first_scalar, rest)) = scalars.split_first() {
+        let mut largest_scalar = first_scalar.clone();
+
+        for scalar in rest {
+            largest_scalar = keep_larger_scalar(&largest_scalar, scalar, options)?;
+        }
+
+        let largest_scalar = &largest_scalar;
+
+        // If we only have scalars, return the largest one
+        // When nulls are propagated, a null scalar makes the entire result null regardless of the arrays
+        let Some(first_array) = arrays_iter.next().filter(|_| {
+            !(options.null_handling == NullHandling::Propagate && largest_scalar.is_null())
+        }) else {
+            return Ok(ColumnarValue::Scalar(largest_scalar.clone()));
+        };
+
+        // Optimization: primitive types are computed in a single pass without intermediate arrays
+        if let Some(largest) = keep_larger_primitive(&arrays, Some(largest_scalar), options)? {
+            return Ok(ColumnarValue::Array(largest));
+        }
+
+        // Optimization: dictionaries are compared by the rank of their values without unpacking them
+        if let Some(largest) = keep_larger_dictionary(&arrays, Some(largest_scalar), options)? {
+            return Ok(ColumnarValue::Array(largest));
+        }
+
+        // Start with the largest value, comparing to the scalar without repeating it for every row
+        largest = keep_larger_than_scalar(first_array.clone(), largest_scalar, options)?;
+    } else if let Some(largest) = keep_larger_primitive(&arrays, None, options)? {
+        // Optimization: primitive types are computed in a single pass without intermediate arrays
+        return Ok(ColumnarValue::Array(largest));
+    } else if let Some(largest) = keep_larger_dictionary(&arrays, None, options)? {
+        // Optimization: dictionaries are compared by the rank of their values without unpacking them
+        return Ok(ColumnarValue::Array(largest));
+    } else {
+        // If we only have arrays, start with the first array
+        // (We must have at least one array, as there are at least 2 arguments)
+        let Some(first_array) = arrays_iter.next() else {
+            return internal_err!("Expected at least one array argument");
+        };
+
+        largest = first_array.clone();
+    }
+
+    for array in arrays_iter {
+        largest = keep_larger(array.clone(), largest, options)?;
+    }
+
+    Ok(ColumnarValue::Array(largest))
+}
+
+/// Return the 1-based position of the argument that holds the largest (or smallest, depending on `options`)
+/// value at each row, or null when the largest value is null
+///
+/// Ties are resolved to the lowest position, so the arguments are compared in the order of their position
+/// and the largest value so far is kept when it is equal to the next argument
+pub(crate) fn keep_larger_index_columnar(args: &[ColumnarValue], options: CompareOptions) -> Result<ColumnarValue> {
+    // DataFusion has arranged for the correct inputs to be passed to this
+    // function, but we check again to make sure, as the function can also be invoked directly
+    if args.len() < 2 {
+        return exec_err!("Expected at least 2 arguments, got {}", args.len());
+    }
+
+    check_same_data_type(args)?;
+
+    // Only the positions are returned, so dictionaries are unpacked to avoid combining their values
+    let args = args.iter().map(unpack_dictionary).collect::<Result<Vec<_>>>()?;
+
+    // Split to scalars and arrays while keeping the 1-based position of each argument
+    let mut scalars = vec![];
+    let mut arrays = vec![];
+
+    for (index, arg) in args.iter().enumerate() {
+        let position = index as u64 + 1;
+
+        match arg {
+            ColumnarValue::Scalar(scalar) => scalars.push((position, scalar)),
+            ColumnarValue::Array(array) => arrays.push((position, array)),
+        }
+    }
+
+    // Optimization: merge all scalars into one to avoid recomputing,
+    // the largest scalar is kept on ties so it is the one with the lowest position
+    let mut largest_scalar = None;
+
+    if let Some((&(first_position, first_scalar), rest)) = scalars.split_first() {
+        let (mut position, mut largest) = (first_position, first_scalar);
+
+        for &(scalar_position, scalar) in rest {
+            if !get_larger_scalar(largest, scalar, options)? {
+                largest = scalar;
+                position = scalar_position;
+            }
+        }
+
+        // If we only have scalars, return the position of the largest one
+        // When nulls are propagated, a null scalar makes the entire result null regardless of the arrays
+        if arrays.is_empty() || (options.null_handling == NullHandling::Propagate && largest.is_null()) {
+            return Ok(ColumnarValue::Scalar(ScalarValue::UInt64(Some(position).filter(|_| !largest.is_null()))));
+        }
+
+        largest_scalar = Some((position, largest));
+    }
+
+    // The arrays before the scalar, the scalar, and then the rest of the arrays
+    let scalar_position = largest_scalar.map_or(u64::MAX, |(position, _)| position);
+    let (before, after): (Vec<_>, Vec<_>) = arrays.into_iter().partition(|(position, _)| *position < scalar_position);
+
+    let mut largest: ArrayRef;
+    let mut largest_position: ArrayRef;
+    let mut rest = after.into_iter();
+
+    if let Some(((first_position, first_array), before_rest)) = before.split_first() {
+        largest = (*first_array).clone();
+        largest_position = Arc::new(UInt64Array::from_value(*first_position, first_array.len()));
+
+        for (position, array) in before_rest {
+            (largest, largest_position) = keep_larger_index(largest, largest_position, array, *position, options)?;
+        }
+
+        if let Some((position, scalar)) = largest_scalar {
+            // Compare to the scalar without repeating it for every row
+            let keep_largest = get_larger_than_scalar(largest.as_ref(), scalar, options)?;
+
+            largest = zip(&keep_largest, &largest, &scalar.to_scalar()?)?;
+            largest_position = zip(&keep_largest, &largest_position, &UInt64Array::new_scalar(position))?;
+        }
+    } else {
+        // There are no arrays before the scalar, so start by comparing the scalar to the first array after it
+        // (We must have both, as there are no arrays before the scalar and at least one array)
+        let (Some((position, scalar)), Some((first_position, first_array))) = (largest_scalar, rest.next()) else {
+            return internal_err!("Expected a scalar and an array argument");
+        };
+
+        let keep_scalar = get_scalar_larger_than(scalar, first_array.as_ref(), options)?;
+
+        largest = zip(&keep_scalar, &scalar.to_scalar()?, first_array)?;
+        largest_position = zip(&keep_scalar, &UInt64Array::new_scalar(position), &UInt64Array::new_scalar(first_position))?;
+    }
+
+    for (position, array) in rest {
+        (largest, largest_position) = keep_larger_index(largest, largest_position, array, position, options)?;
+    }
+
+    // The position is null when the largest value is null
+    let largest_position = UInt64Array::new(
+        largest_position.as_primitive::<UInt64Type>().values().clone(),
+        largest.logical_nulls(),
+    );
+
+    Ok(ColumnarValue::Array(Arc::new(largest_position)))
+}
+
+/// Keep the largest value so far and its position, or the value of the array and its position when it is larger
+fn keep_larger_index(largest: ArrayRef, largest_position: ArrayRef, array: &ArrayRef, position: u64, options: CompareOptions) -> Result<(ArrayRef, ArrayRef)> {
+    let keep_largest = get_larger(largest.as_ref(), array.as_ref(), options)?;
+
+    Ok((
+        zip(&keep_largest, &largest, array)?,
+        zip(&keep_largest, &largest_position, &UInt64Array::new_scalar(position))?,
+    ))
+}
+
+/// Cast a dictionary argument to its value type
+fn unpack_dictionary(arg: &ColumnarValue) -> Result<ColumnarValue> {
+    Ok(match arg {
+        ColumnarValue::Array(array) => match array.data_type() {
+            DataType::Dictionary(_, value_type) => ColumnarValue::Array(cast(array, value_type)?),
+            _ => arg.clone(),
+        },
+        ColumnarValue::Scalar(ScalarValue::Dictionary(_, value)) => ColumnarValue::Scalar(value.as_ref().clone()),
+        ColumnarValue::Scalar(_) => arg.clone(),
+    })
+}
+
+/// Return the value at the same position as the largest (or smallest, depending on `options`) key at each row,
+/// or null when the largest key is null
+///
+/// Ties are resolved to the lowest position the same as [`keep_larger_index_columnar`],
+/// and the values don't have to be of the same type as the keys
+pub(crate) fn keep_larger_by_columnar(keys: &[ColumnarValue], values: &[ColumnarValue], options: CompareOptions) -> Result<ColumnarValue> {
+    if keys.len() != values.len() {
+        return exec_err!("Expected the same number of keys and values, got {} keys and {} values", keys.len(), values.len());
+    }
+
+    if !values.is_empty() {
+        check_same_data_type(values)?;
+    }
+
+    let positions = keep_larger_index_columnar(keys, options)?;
+
+    let positions = match positions {
+        ColumnarValue::Scalar(ScalarValue::UInt64(position)) => {
+            // All the keys are scalars, so the position is the same for all the rows
+            return match position {
+                Some(position) => Ok(values[position as usize - 1].clone()),
+                None => Ok(ColumnarValue::Scalar(ScalarValue::try_from(&values[0].data_type())?)),
+            };
+        }
+        ColumnarValue::Scalar(scalar) => return internal_err!("Expected a UInt64 position, got {:?}", scalar),
+        ColumnarValue::Array(positions) => positions,
+    };
+
+    let positions = positions.as_primitive::<UInt64Type>();
+
+    let mut arrays = values
+        .iter()
+        .map(|value| value.clone().into_array(positions.len()))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Rows where the largest key is null take the value from an all null array
+    let null_index = arrays.len();
+    arrays.push(new_null_array(&values[0].data_type(), 1));
+
+    let indices = positions
+        .iter()
+        .enumerate()
+        .map(|(row, position)| match position {
+            Some(position) => (position as usize - 1, row),
+            None => (null_index, 0),
+        })
+        .collect::<Vec<_>>();
+
+    let arrays = arrays.iter().map(|array| array.as_ref()).collect::<Vec<_>>();
+
+    Ok(ColumnarValue::Array(interleave(&arrays, &indices)?))
+}
+
+/// Keep the larger value of every 2 arrays at a time, creating a new array for every extra array
+///
+/// This is how `greatest` of many arrays was computed before the single pass kernels,
+/// it is only public with the `bench` feature so the benchmarks can compare against it
+#[cfg(feature = "bench")]
+pub fn keep_larger_pairwise(arrays: &[ArrayRef], null_handling: NullHandling) -> Result<ArrayRef> {
+    let options = CompareOptions::new(crate::helpers::KeepOrder::Larger, null_handling, crate::NanOrdering::default());
+
+    let Some((first, rest)) = arrays.split_first() else {
+        return exec_err!("Expected at least 1 array, got 0");
+    };
+
+    let mut largest = first.clone();
+
+    for array in rest {
+        largest = keep_larger(array.clone(), largest, options)?;
+    }
+
+    Ok(largest)
+}
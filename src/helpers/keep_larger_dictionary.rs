@@ -0,0 +1,313 @@
+use crate::helpers::{contains_float, get_larger, CompareOptions, KeepOrder};
+use crate::traits::NullBufferExt;
+use crate::NullHandling;
+use datafusion::arrow::array::{make_array, Array, ArrayData, ArrayRef, AsArray, UInt32Array};
+use datafusion::arrow::buffer::NullBuffer;
+use datafusion::arrow::compute::kernels::rank::rank;
+use datafusion::arrow::compute::kernels::zip::zip;
+use datafusion::arrow::compute::{cast_with_options, concat, take, CastOptions, SortOptions};
+use datafusion::arrow::datatypes::{DataType, UInt32Type};
+use datafusion::error::Result;
+use datafusion_common::ScalarValue;
+
+/// Return the largest (or smallest, depending on `options`) value at each row across dictionary arrays
+/// and the scalar that share the same data type, without unpacking the dictionaries,
+/// or `None` if the data type is not supported or the arrays have different lengths
+///
+/// The values of all the dictionaries are ranked once, and the rows are compared by the rank of their value,
+/// the output is a dictionary of the values that are used by the largest rows.
+/// Values that can't be ranked, such as floating point values where NaN is ranked differently than it is compared,
+/// are compared unpacked instead.
+///
+/// When the used values don't fit the key type the output is unpacked to the value type, as it can't be a dictionary.
+///
+/// Ties are resolved the same way as folding the arrays with [`keep_larger`](crate::helpers::keep_larger)
+pub(crate) fn keep_larger_dictionary(arrays: &[&ArrayRef], scalar: Option<&ScalarValue>, options: CompareOptions) -> Result<Option<ArrayRef>> {
+    let Some(data_type) = arrays.first().map(|array| array.data_type()) else {
+        return Ok(None);
+    };
+
+    let DataType::Dictionary(key_type, value_type) = data_type else {
+        return Ok(None);
+    };
+
+    if arrays.iter().any(|array| array.data_type() != data_type || array.len() != arrays[0].len()) || scalar.is_some_and(|scalar| scalar.data_type() != *data_type) {
+        return Ok(None);
+    }
+
+    let len = arrays.iter().map(|array| array.len()).min().unwrap_or(0);
+
+    // The scalar is compared first, and it is repeated using a single dictionary value
+    let scalar = scalar.map(|scalar| scalar.to_array_of_size(len)).transpose()?;
+    let inputs = scalar
+        .iter()
+        .chain(arrays.iter().copied())
+        .map(|array| array.slice(0, len))
+        .collect::<Vec<_>>();
+
+    let dictionaries = inputs.iter().map(|array| array.as_any_dictionary()).collect::<Vec<_>>();
+
+    let all_values = concat(&dictionaries.iter().map(|dictionary| dictionary.values().as_ref()).collect::<Vec<_>>())?;
+
+    // The index in all the values of the value of each row, null when the row has no value
+    let mut values_offset = 0;
+    let indices = dictionaries
+        .iter()
+        .zip(&inputs)
+        .map(|(dictionary, input)| {
+            let nulls = input.logical_nulls();
+            let indices = dictionary
+                .normalized_keys()
+                .into_iter()
+                .enumerate()
+                .map(|(row, key)| if nulls.as_ref().is_some_and(|nulls| nulls.is_null(row)) { 0 } else { (values_offset + key) as u32 })
+                .collect::<Vec<_>>();
+
+            values_offset += dictionary.values().len();
+
+            UInt32Array::new(indices.into(), nulls)
+        })
+        .collect::<Vec<_>>();
+
+    // Rank the values of all the dictionaries together, so equal values have the same rank
+    let ranks = if contains_float(value_type) { None } else { rank(&all_values, Some(SortOptions::default())).ok() };
+
+    let largest = match ranks {
+        Some(ranks) => largest_by_rank(&indices, &ranks, options),
+        None => largest_unpacked(&all_values, &indices, options)?,
+    };
+
+    let validity = largest.nulls().filter(|validity| validity.null_count() > 0).cloned();
+
+    // Keep only the values that are used, in the order they first appear
+    let mut new_keys = vec![u32::MAX; all_values.len()];
+    let mut used_values = vec![];
+
+    let keys = largest
+        .values()
+        .iter()
+        .enumerate()
+        .map(|(row, &value)| {
+            if validity.as_ref().is_some_and(|validity| validity.is_null(row)) {
+                return 0;
+            }
+
+            let value = value as usize;
+
+            if new_keys[value] == u32::MAX {
+                new_keys[value] = used_values.len() as u32;
+                used_values.push(value as u32);
+            }
+
+            new_keys[value]
+        })
+        .collect::<Vec<_>>();
+
+    let keys = UInt32Array::new(keys.into(), validity);
+
+    // Too many used values for the key type, so return the values unpacked
+    let Ok(keys) = cast_with_options(&keys, key_type, &CastOptions { safe: false, ..Default::default() }) else {
+        return Ok(Some(take(&all_values, &largest, None)?));
+    };
+
+    let values = take(&all_values, &UInt32Array::from(used_values), None)?;
+
+    let data = ArrayData::builder(data_type.clone())
+        .len(len)
+        .nulls(keys.nulls().cloned())
+        .add_buffer(keys.to_data().buffers()[0].clone())
+        .add_child_data(values.to_data())
+        .build()?;
+
+    Ok(Some(make_array(data)))
+}
+
+/// Return the index of the largest value at each row by comparing the ranks of the values
+fn largest_by_rank(indices: &[UInt32Array], ranks: &[u32], options: CompareOptions) -> UInt32Array {
+    let Some((first, rest)) = indices.split_first() else {
+        return UInt32Array::from(Vec::<u32>::new());
+    };
+
+    let mut largest = first.values().to_vec();
+    let mut validity = first.nulls().cloned();
+
+    for indices in rest {
+        let nulls = indices.nulls();
+
+        for (row, (largest, &value)) in largest.iter_mut().zip(indices.values()).enumerate() {
+            let value_is_null = nulls.is_some_and(|nulls| nulls.is_null(row));
+            let largest_is_null = validity.as_ref().is_some_and(|validity| validity.is_null(row));
+
+            let keep_value = options.keep_lhs(value_is_null, largest_is_null, || match options.order {
+                KeepOrder::Larger => ranks[value as usize] >= ranks[*largest as usize],
+                KeepOrder::Smaller => ranks[value as usize] <= ranks[*largest as usize],
+            });
+
+            if keep_value {
+                *largest = value;
+            }
+        }
+
+        validity = match options.null_handling {
+            NullHandling::Skip => NullBuffer::union_prefer_not_null(validity.as_ref(), nulls),
+            NullHandling::Propagate => NullBuffer::union(validity.as_ref(), nulls),
+        };
+    }
+
+    UInt32Array::new(largest.into(), validity)
+}
+
+/// Return the index of the largest value at each row by comparing the unpacked values
+fn largest_unpacked(all_values: &ArrayRef, indices: &[UInt32Array], options: CompareOptions) -> Result<UInt32Array> {
+    let Some((first, rest)) = indices.split_first() else {
+        return Ok(UInt32Array::from(Vec::<u32>::new()));
+    };
+
+    let mut largest_indices = first.clone();
+    let mut largest = take(all_values, &largest_indices, None)?;
+
+    for indices in rest {
+        let values = take(all_values, indices, None)?;
+        let keep_values = get_larger(values.as_ref(), largest.as_ref(), options)?;
+
+        largest_indices = zip(&keep_values, indices, &largest_indices)?.as_primitive::<UInt32Type>().clone();
+        largest = zip(&keep_values, &values, &largest)?;
+    }
+
+    Ok(largest_indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::utils::{self, generate_optional_values, generate_string_values};
+    use datafusion::arrow::array::{DictionaryArray, Float64Array, ListArray, StringArray};
+    use datafusion::arrow::compute::cast;
+    use datafusion::arrow::datatypes::{Int32Type, Int8Type};
+    use std::sync::Arc;
+
+    fn create_dictionary(values: Vec<Option<String>>) -> ArrayRef {
+        Arc::new(values.iter().map(|value| value.as_deref()).collect::<DictionaryArray<Int32Type>>())
+    }
+
+    fn unpack(array: &ArrayRef) -> ArrayRef {
+        let DataType::Dictionary(_, value_type) = array.data_type() else {
+            return array.clone();
+        };
+
+        cast(array, value_type).unwrap()
+    }
+
+    fn assert_same_as_fold(arrays: &[ArrayRef], scalar: Option<&ScalarValue>) {
+        utils::assert_same_as_fold(arrays, scalar, keep_larger_dictionary, unpack);
+    }
+
+    #[test]
+    fn same_as_fold_for_strings() {
+        let arrays = (0..4)
+            .map(|_| create_dictionary(generate_string_values(200, 0..2, Some(0.3))))
+            .collect::<Vec<_>>();
+
+        let scalar = |value: Option<&str>| ScalarValue::Dictionary(
+            Box::new(DataType::Int32),
+            Box::new(ScalarValue::Utf8(value.map(|value| value.to_string()))),
+        );
+
+        assert_same_as_fold(&arrays, None);
+        assert_same_as_fold(&arrays, Some(&scalar(Some("a"))));
+        assert_same_as_fold(&arrays, Some(&scalar(None)));
+    }
+
+    #[test]
+    fn null_values_in_the_dictionary() {
+        // Null values are referenced by valid keys
+        let values = Arc::new(StringArray::from(vec![Some("b"), None, Some("a")]));
+
+        let arrays = [
+            Arc::new(DictionaryArray::<Int32Type>::try_new(vec![0, 1, 2, 1].into(), values.clone()).unwrap()) as ArrayRef,
+            Arc::new(DictionaryArray::<Int32Type>::try_new(vec![Some(1), Some(2), None, Some(0)].into(), values).unwrap()) as ArrayRef,
+        ];
+
+        assert_same_as_fold(&arrays, None);
+    }
+
+    #[test]
+    fn only_used_values_are_kept() {
+        let arrays = [
+            create_dictionary(vec![Some("a".to_string()), Some("x".to_string())]),
+            create_dictionary(vec![Some("b".to_string()), Some("c".to_string())]),
+        ];
+
+        let largest = keep_larger_dictionary(&arrays.iter().collect::<Vec<_>>(), None, CompareOptions::default()).unwrap().unwrap();
+
+        assert_eq!(largest.as_any_dictionary().values().as_ref(), &StringArray::from(vec!["b", "x"]));
+    }
+
+    #[test]
+    fn unpacked_when_used_values_do_not_fit_the_key_type() {
+        // Each dictionary has less than 128 values, but together the largest values are 200 different values
+        let a = (0..200).map(|i| if i < 100 { format!("z{}", i) } else { "a".to_string() }).collect::<Vec<_>>();
+        let b = (0..200).map(|i| if i < 100 { "b".to_string() } else { format!("y{}", i) }).collect::<Vec<_>>();
+
+        let arrays = [
+            Arc::new(a.iter().map(|value| value.as_str()).collect::<DictionaryArray<Int8Type>>()) as ArrayRef,
+            Arc::new(b.iter().map(|value| value.as_str()).collect::<DictionaryArray<Int8Type>>()) as ArrayRef,
+        ];
+
+        for options in utils::all_compare_options() {
+            let expected = utils::fold_pairwise(&arrays.iter().collect::<Vec<_>>(), None, options, unpack);
+            let actual = keep_larger_dictionary(&arrays.iter().collect::<Vec<_>>(), None, options).unwrap().unwrap();
+
+            assert_eq!(&unpack(&actual), &expected, "{:?}", options);
+        }
+
+        let largest = keep_larger_dictionary(&arrays.iter().collect::<Vec<_>>(), None, CompareOptions::default()).unwrap().unwrap();
+
+        assert_eq!(largest.data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn same_as_fold_for_values_that_can_not_be_ranked() {
+        let lists = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(1)]),
+            Some(vec![None]),
+            None,
+            Some(vec![]),
+        ])) as ArrayRef;
+
+        let arrays = [
+            Arc::new(DictionaryArray::<Int32Type>::try_new(vec![Some(0), Some(1), None, Some(4), Some(3)].into(), lists.clone()).unwrap()) as ArrayRef,
+            Arc::new(DictionaryArray::<Int32Type>::try_new(vec![Some(2), Some(0), Some(3), None, Some(1)].into(), lists).unwrap()) as ArrayRef,
+        ];
+
+        assert_same_as_fold(&arrays, None);
+    }
+
+    #[test]
+    fn unsupported_types() {
+        let strings = Arc::new(StringArray::from(vec!["a"])) as ArrayRef;
+
+        assert!(keep_larger_dictionary(&[&strings, &strings], None, CompareOptions::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn floats_with_nan_same_as_unpacked() {
+        let values = Arc::new(Float64Array::from(vec![f64::NAN, -f64::NAN, 0.0, -0.0, 1.0, f64::INFINITY, f64::NEG_INFINITY]));
+
+        let arrays = (0..3)
+            .map(|_| {
+                let keys = generate_optional_values::<u8>(200, Some(0.2)).into_iter().map(|key| key.map(|key| key as i32 % values.len() as i32)).collect::<Vec<_>>();
+
+                Arc::new(DictionaryArray::<Int32Type>::try_new(keys.into(), values.clone()).unwrap()) as ArrayRef
+            })
+            .collect::<Vec<_>>();
+
+        let scalar = |value: f64| ScalarValue::Dictionary(Box::new(DataType::Int32), Box::new(ScalarValue::Float64(Some(value))));
+
+        // Floating point values are compared unpacked, so the NaN ordering is respected
+        assert_same_as_fold(&arrays, None);
+        assert_same_as_fold(&arrays, Some(&scalar(f64::NAN)));
+        assert_same_as_fold(&arrays, Some(&scalar(0.5)));
+    }
+}
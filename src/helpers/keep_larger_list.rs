@@ -0,0 +1,74 @@
+use crate::helpers::{nan_mask, normalize_nested_nan, CompareOptions, SORT_OPTIONS};
+use datafusion::arrow::array::{make_comparator, new_null_array, Array, ArrayRef, AsArray, UInt64Array};
+use datafusion::arrow::compute::take;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::exec_err;
+use std::ops::Range;
+
+/// Return the largest (or smallest, depending on `options`) item of each list in `array`,
+/// or null when the list is null or empty
+///
+/// Items are compared the same way as the arguments of `greatest`,
+/// so nulls are either skipped or propagated and NaN items are ordered depending on `options`
+pub(crate) fn keep_larger_in_list(array: &dyn Array, options: CompareOptions) -> Result<ArrayRef> {
+    let (values, ranges): (&ArrayRef, Vec<Range<usize>>) = match array.data_type() {
+        DataType::List(_) => {
+            let list = array.as_list::<i32>();
+            (list.values(), list.value_offsets().windows(2).map(|w| w[0] as usize..w[1] as usize).collect())
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_list::<i64>();
+            (list.values(), list.value_offsets().windows(2).map(|w| w[0] as usize..w[1] as usize).collect())
+        }
+        DataType::FixedSizeList(_, size) => {
+            let list = array.as_fixed_size_list();
+            let size = *size as usize;
+            let offset = list.offset();
+            (list.values(), (0..list.len()).map(|i| (offset + i) * size..(offset + i + 1) * size).collect())
+        }
+        data_type => return exec_err!("Expected a list, got {}", data_type),
+    };
+
+    // All the items are null so there is nothing to compare, the Null type has no order
+    if values.data_type().is_null() {
+        return Ok(new_null_array(values.data_type(), array.len()));
+    }
+
+    let list_nulls = array.logical_nulls();
+    let item_nulls = values.logical_nulls();
+    let item_nan = nan_mask(values.as_ref()).filter(|nan| nan.count_set_bits() > 0);
+
+    // NaN items inside nested items should be ordered the same way as NaN items
+    let normalized = normalize_nested_nan(values, options.nan_ordering)?;
+
+    // The comparator always order nulls first so nested items (e.g. struct fields) have the same order
+    // for both greatest and least, top level nulls are handled by the options
+    let cmp = make_comparator(normalized.as_ref(), normalized.as_ref(), SORT_OPTIONS)?;
+
+    let is_null = |i: usize| item_nulls.as_ref().is_some_and(|nulls| nulls.is_null(i));
+    let is_nan = |i: usize| item_nan.as_ref().is_some_and(|nan| nan.value(i));
+
+    let indices = ranges
+        .into_iter()
+        .enumerate()
+        .map(|(row, range)| {
+            if list_nulls.as_ref().is_some_and(|nulls| nulls.is_null(row)) {
+                return None;
+            }
+
+            // Keep the later item on ties, the same as the later argument of `greatest`
+            range.map(|i| i as u64).reduce(|largest, i| {
+                let (lhs, rhs) = (i as usize, largest as usize);
+
+                let keep_lhs = options.keep_lhs(is_null(lhs), is_null(rhs), || {
+                    options.keep_lhs_not_null(is_nan(lhs), is_nan(rhs), || cmp(lhs, rhs))
+                });
+
+                if keep_lhs { i } else { largest }
+            })
+        })
+        .collect::<UInt64Array>();
+
+    Ok(take(values.as_ref(), &indices, None)?)
+}
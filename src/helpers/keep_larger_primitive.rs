@@ -0,0 +1,228 @@
+use crate::helpers::CompareOptions;
+use crate::traits::NullBufferExt;
+use crate::NullHandling;
+use datafusion::arrow::array::{new_null_array, Array, ArrayRef, ArrowNativeTypeOp, ArrowPrimitiveType, AsArray, PrimitiveArray};
+use datafusion::arrow::buffer::{NullBuffer, ScalarBuffer};
+use datafusion::arrow::datatypes::*;
+use datafusion::error::Result;
+use datafusion_common::ScalarValue;
+use std::sync::Arc;
+
+/// Return the largest (or smallest, depending on `options`) value at each row across all the arrays and the scalar
+/// in a single pass that writes directly into one output buffer, or `None` if the data type is not supported or the arrays have different lengths
+///
+/// This is the same as folding the arrays with [`keep_larger`](crate::helpers::keep_larger) starting from the scalar
+/// (or from the first array when there is no scalar), including how ties are resolved,
+/// but without creating intermediate arrays for every argument.
+///
+/// Supported data types are integers, floating points, decimals, dates, times, timestamps and durations
+pub(crate) fn keep_larger_primitive(arrays: &[&ArrayRef], scalar: Option<&ScalarValue>, options: CompareOptions) -> Result<Option<ArrayRef>> {
+    let Some(data_type) = arrays.first().map(|array| array.data_type()) else {
+        return Ok(None);
+    };
+
+    if arrays.iter().any(|array| array.data_type() != data_type || array.len() != arrays[0].len()) || scalar.is_some_and(|scalar| scalar.data_type() != *data_type) {
+        return Ok(None);
+    }
+
+    macro_rules! keep_larger_typed {
+        ($t:ty) => {
+            keep_larger_primitive_typed::<$t>(arrays, scalar, options).map(Some)
+        };
+    }
+
+    match data_type {
+        DataType::Int8 => keep_larger_typed!(Int8Type),
+        DataType::Int16 => keep_larger_typed!(Int16Type),
+        DataType::Int32 => keep_larger_typed!(Int32Type),
+        DataType::Int64 => keep_larger_typed!(Int64Type),
+        DataType::UInt8 => keep_larger_typed!(UInt8Type),
+        DataType::UInt16 => keep_larger_typed!(UInt16Type),
+        DataType::UInt32 => keep_larger_typed!(UInt32Type),
+        DataType::UInt64 => keep_larger_typed!(UInt64Type),
+        DataType::Float16 => keep_larger_typed!(Float16Type),
+        DataType::Float32 => keep_larger_typed!(Float32Type),
+        DataType::Float64 => keep_larger_typed!(Float64Type),
+        DataType::Decimal128(_, _) => keep_larger_typed!(Decimal128Type),
+        DataType::Decimal256(_, _) => keep_larger_typed!(Decimal256Type),
+        DataType::Date32 => keep_larger_typed!(Date32Type),
+        DataType::Date64 => keep_larger_typed!(Date64Type),
+        DataType::Time32(TimeUnit::Second) => keep_larger_typed!(Time32SecondType),
+        DataType::Time32(TimeUnit::Millisecond) => keep_larger_typed!(Time32MillisecondType),
+        DataType::Time64(TimeUnit::Microsecond) => keep_larger_typed!(Time64MicrosecondType),
+        DataType::Time64(TimeUnit::Nanosecond) => keep_larger_typed!(Time64NanosecondType),
+        DataType::Timestamp(TimeUnit::Second, _) => keep_larger_typed!(TimestampSecondType),
+        DataType::Timestamp(TimeUnit::Millisecond, _) => keep_larger_typed!(TimestampMillisecondType),
+        DataType::Timestamp(TimeUnit::Microsecond, _) => keep_larger_typed!(TimestampMicrosecondType),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => keep_larger_typed!(TimestampNanosecondType),
+        DataType::Duration(TimeUnit::Second) => keep_larger_typed!(DurationSecondType),
+        DataType::Duration(TimeUnit::Millisecond) => keep_larger_typed!(DurationMillisecondType),
+        DataType::Duration(TimeUnit::Microsecond) => keep_larger_typed!(DurationMicrosecondType),
+        DataType::Duration(TimeUnit::Nanosecond) => keep_larger_typed!(DurationNanosecondType),
+        _ => Ok(None),
+    }
+}
+
+fn keep_larger_primitive_typed<T: ArrowPrimitiveType>(arrays: &[&ArrayRef], scalar: Option<&ScalarValue>, options: CompareOptions) -> Result<ArrayRef> {
+    let len = arrays.iter().map(|array| array.len()).min().unwrap_or(0);
+
+    let mut arrays_iter = arrays.iter().map(|array| array.as_primitive::<T>());
+
+    // The current largest value of each row and which rows have a value,
+    // `None` validity means all the rows have a value
+    let (mut largest, mut validity): (Vec<T::Native>, Option<NullBuffer>) = match scalar {
+        // When nulls are skipped, a null scalar is the same as not having one
+        Some(scalar) if !scalar.is_null() => {
+            let scalar = scalar.to_array()?;
+            (vec![scalar.as_primitive::<T>().value(0); len], None)
+        }
+
+        // A null scalar makes all the rows null when nulls are propagated
+        Some(_) if options.null_handling == NullHandling::Propagate => {
+            return Ok(new_null_array(arrays[0].data_type(), len));
+        }
+
+        _ => match arrays_iter.next() {
+            Some(first) => (first.values()[..len].to_vec(), first.nulls().map(|nulls| nulls.slice(0, len))),
+            None => (vec![], None),
+        },
+    };
+
+    // NaN is the only value that is not equal to itself, this is always false for non floating point types
+    #[allow(clippy::eq_op)]
+    let is_nan = |value: T::Native| value != value;
+
+    let keep_lhs_not_null = |lhs: T::Native, rhs: T::Native| {
+        options.keep_lhs_not_null(is_nan(lhs), is_nan(rhs), || lhs.compare(rhs))
+    };
+
+    for array in arrays_iter {
+        let values = &array.values()[..len];
+        let nulls = array.nulls().map(|nulls| nulls.slice(0, len));
+
+        match (options.null_handling, &nulls, &validity) {
+            // Neither side has nulls, or the nulls are propagated so the values of the null rows does not matter
+            (NullHandling::Propagate, _, _) | (NullHandling::Skip, None, None) => {
+                for (largest, &value) in largest.iter_mut().zip(values) {
+                    if keep_lhs_not_null(value, *largest) {
+                        *largest = value;
+                    }
+                }
+            }
+
+            // Keep the new value if it is not null and the current one is null or smaller
+            (NullHandling::Skip, _, _) => {
+                for (i, (largest, &value)) in largest.iter_mut().zip(values).enumerate() {
+                    let value_is_null = nulls.as_ref().is_some_and(|nulls| nulls.is_null(i));
+                    let largest_is_null = validity.as_ref().is_some_and(|validity| validity.is_null(i));
+
+                    if options.keep_lhs(value_is_null, largest_is_null, || keep_lhs_not_null(value, *largest)) {
+                        *largest = value;
+                    }
+                }
+            }
+        }
+
+        // Update the validity of all the rows at once
+        validity = match options.null_handling {
+            // A row has a value if any of the inputs has a value
+            NullHandling::Skip => NullBuffer::union_prefer_not_null(validity.as_ref(), nulls.as_ref()),
+
+            // A row has a value only if all the inputs have a value
+            NullHandling::Propagate => NullBuffer::union(validity.as_ref(), nulls.as_ref()),
+        };
+    }
+
+    let nulls = validity.filter(|nulls| nulls.null_count() > 0);
+
+    let result = PrimitiveArray::<T>::new(ScalarBuffer::from(largest), nulls)
+        .with_data_type(arrays[0].data_type().clone());
+
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::utils::{self, create_primitive_array, generate_optional_values};
+    use datafusion::arrow::array::{Decimal128Array, TimestampMillisecondArray};
+
+    fn assert_same_as_fold(arrays: &[ArrayRef], scalar: Option<&ScalarValue>) {
+        utils::assert_same_as_fold(arrays, scalar, keep_larger_primitive, |array| array.clone());
+    }
+
+    #[test]
+    fn same_as_fold_for_integers() {
+        let arrays = (0..5)
+            .map(|_| create_primitive_array::<Int32Type>(generate_optional_values::<i8>(200, Some(0.3)).into_iter().map(|v| v.map(|v| (v % 8) as i32)).collect()) as ArrayRef)
+            .collect::<Vec<_>>();
+
+        assert_same_as_fold(&arrays, None);
+        assert_same_as_fold(&arrays, Some(&ScalarValue::Int32(Some(3))));
+        assert_same_as_fold(&arrays, Some(&ScalarValue::Int32(None)));
+    }
+
+    #[test]
+    fn same_as_fold_for_floats_with_nan() {
+        let special = [f64::NAN, -f64::NAN, 0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY];
+
+        let arrays = (0..4)
+            .map(|_| {
+                let values = generate_optional_values::<u8>(300, Some(0.2))
+                    .into_iter()
+                    .map(|v| v.map(|v| special.get(v as usize % 10).copied().unwrap_or(v as f64)))
+                    .collect();
+
+                create_primitive_array::<Float64Type>(values) as ArrayRef
+            })
+            .collect::<Vec<_>>();
+
+        assert_same_as_fold(&arrays, None);
+        assert_same_as_fold(&arrays, Some(&ScalarValue::Float64(Some(f64::NAN))));
+        assert_same_as_fold(&arrays, Some(&ScalarValue::Float64(Some(100.0))));
+    }
+
+    #[test]
+    fn same_as_fold_for_sliced_arrays() {
+        let arrays = (0..3)
+            .map(|offset| {
+                let array = create_primitive_array::<Int64Type>(generate_optional_values::<i64>(100, Some(0.3)));
+                Arc::new(array.slice(offset, 50)) as ArrayRef
+            })
+            .collect::<Vec<_>>();
+
+        assert_same_as_fold(&arrays, None);
+        assert_same_as_fold(&arrays, Some(&ScalarValue::Int64(Some(0))));
+    }
+
+    #[test]
+    fn keep_data_type_parameters() {
+        let timestamps = [
+            Arc::new(TimestampMillisecondArray::from(vec![Some(1), None, Some(5)]).with_timezone("+02:00")) as ArrayRef,
+            Arc::new(TimestampMillisecondArray::from(vec![Some(3), Some(2), None]).with_timezone("+02:00")) as ArrayRef,
+        ];
+
+        assert_same_as_fold(&timestamps, None);
+
+        let decimals = [
+            Arc::new(Decimal128Array::from(vec![Some(100), None, Some(-5)]).with_precision_and_scale(10, 2).unwrap()) as ArrayRef,
+            Arc::new(Decimal128Array::from(vec![Some(30), Some(2), None]).with_precision_and_scale(10, 2).unwrap()) as ArrayRef,
+        ];
+
+        assert_same_as_fold(&decimals, Some(&ScalarValue::Decimal128(Some(50), 10, 2)));
+    }
+
+    #[test]
+    fn unsupported_types() {
+        let strings: ArrayRef = Arc::new(datafusion::arrow::array::StringArray::from(vec!["a", "b"]));
+
+        assert!(keep_larger_primitive(&[&strings, &strings], None, CompareOptions::default()).unwrap().is_none());
+
+        // Different types are not supported
+        let int32: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(1)]);
+        let int64: ArrayRef = create_primitive_array::<Int64Type>(vec![Some(1)]);
+
+        assert!(keep_larger_primitive(&[&int32, &int64], None, CompareOptions::default()).unwrap().is_none());
+        assert!(keep_larger_primitive(&[&int32], Some(&ScalarValue::Int64(Some(1))), CompareOptions::default()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::helpers::{keep_larger_columnar, CompareOptions};
+use crate::{LengthMismatch, NullHandling};
+use datafusion::arrow::array::{Array, ArrayRef, AsArray, ListArray, UInt64Array};
+use datafusion::arrow::buffer::{NullBuffer, OffsetBuffer};
+use datafusion::arrow::compute::take;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::{exec_err, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use std::sync::Arc;
+
+/// Return a list where the i-th item is the largest (or smallest, depending on `options`) i-th item of the lists in `args`
+///
+/// The items of each list are aligned by their position in the row, padded with NULL according to `length_mismatch`,
+/// and then compared the same way as the arguments of `greatest`.
+/// A NULL list is skipped or propagated like a NULL argument of `greatest`.
+pub(crate) fn keep_larger_zip(args: &[ColumnarValue], options: CompareOptions, length_mismatch: LengthMismatch) -> Result<ColumnarValue> {
+    // DataFusion has arranged for the correct inputs to be passed to this
+    // function, but we check again to make sure, as the function can also be invoked directly
+    if args.len() < 2 {
+        return exec_err!("Expected at least 2 arguments, got {}", args.len());
+    }
+
+    let DataType::List(field) = args[0].data_type() else {
+        return exec_err!("Expected list arguments, got {}", args[0].data_type());
+    };
+
+    if let Some(arg) = args.iter().find(|arg| arg.data_type() != args[0].data_type()) {
+        return exec_err!("Expected all arguments to have the same data type, got {} and {}", args[0].data_type(), arg.data_type());
+    }
+
+    // Scalars are repeated for every row, when there are only scalars the result is a single row scalar
+    let all_scalars = args.iter().all(|arg| matches!(arg, ColumnarValue::Scalar(_)));
+    let num_rows = args
+        .iter()
+        .find_map(|arg| match arg {
+            ColumnarValue::Array(array) => Some(array.len()),
+            ColumnarValue::Scalar(_) => None,
+        })
+        .unwrap_or(1);
+
+    let arrays = args
+        .iter()
+        .map(|arg| arg.clone().into_array(num_rows))
+        .collect::<Result<Vec<_>>>()?;
+    let lists = arrays.iter().map(|array| array.as_list::<i32>()).collect::<Vec<_>>();
+
+    // The result row is null like the result of `greatest` when the lists are the arguments
+    let nulls = lists.iter().map(|list| list.logical_nulls()).collect::<Vec<_>>();
+    let is_null = |list: usize, row: usize| nulls[list].as_ref().is_some_and(|nulls| nulls.is_null(row));
+
+    let mut row_nulls = Vec::with_capacity(num_rows);
+    let mut row_lengths = Vec::with_capacity(num_rows);
+
+    for row in 0..num_rows {
+        let row_is_null = match options.null_handling {
+            NullHandling::Skip => (0..lists.len()).all(|list| is_null(list, row)),
+            NullHandling::Propagate => (0..lists.len()).any(|list| is_null(list, row)),
+        };
+
+        let mut lengths = (0..lists.len())
+            .filter(|&list| !is_null(list, row))
+            .map(|list| lists[list].value_length(row) as usize);
+
+        let first_length = lengths.next().unwrap_or(0);
+        let mut row_length = first_length;
+
+        for length in lengths {
+            if length != first_length && length_mismatch == LengthMismatch::Error {
+                return exec_err!("Cannot compare lists of different lengths item by item, got {} and {} items at row {}", first_length, length, row);
+            }
+
+            row_length = row_length.max(length);
+        }
+
+        row_nulls.push(!row_is_null);
+        row_lengths.push(if row_is_null { 0 } else { row_length });
+    }
+
+    // Align the items of all the lists so the i-th item of each row is at the same position
+    let aligned = lists
+        .iter()
+        .enumerate()
+        .map(|(list_index, list)| {
+            let indices = row_lengths
+                .iter()
+                .enumerate()
+                .flat_map(|(row, &row_length)| {
+                    let start = list.value_offsets()[row] as u64;
+                    let length = if is_null(list_index, row) { 0 } else { list.value_length(row) as usize };
+
+                    (0..row_length).map(move |i| Some(start + i as u64).filter(|_| i < length))
+                })
+                .collect::<UInt64Array>();
+
+            Ok(ColumnarValue::Array(take(list.values().as_ref(), &indices, None)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let items_count = row_lengths.iter().sum();
+    let items = keep_larger_columnar(&aligned, options)?.into_array(items_count)?;
+
+    // Padded items are null even if the items of the arguments are not nullable
+    let result: ArrayRef = Arc::new(ListArray::try_new(
+        Arc::new(field.as_ref().clone().with_nullable(true)),
+        OffsetBuffer::from_lengths(row_lengths),
+        items,
+        Some(NullBuffer::from(row_nulls)).filter(|nulls| nulls.null_count() > 0),
+    )?);
+
+    if all_scalars {
+        return Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(result.as_ref(), 0)?));
+    }
+
+    Ok(ColumnarValue::Array(result))
+}
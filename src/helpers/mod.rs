@@ -1,11 +1,37 @@
+mod bounds;
 mod coerce_type_helper;
+mod comparable;
 mod compare_greater;
+mod keep_larger_columnar;
+mod keep_larger_dictionary;
+mod keep_larger_list;
+mod keep_larger_zip;
+mod keep_larger_primitive;
+mod nan;
+mod nullable;
+mod output_ordering;
+mod simplify;
 mod transpose;
+mod udf_options;
 mod permutation;
 mod create_vec_with_literals;
 
+pub(crate) use bounds::*;
 pub(crate) use coerce_type_helper::*;
+pub(crate) use comparable::*;
 pub(crate) use compare_greater::*;
+pub(crate) use keep_larger_columnar::*;
+#[cfg(feature = "bench")]
+pub use keep_larger_columnar::keep_larger_pairwise;
+pub(crate) use keep_larger_dictionary::*;
+pub(crate) use keep_larger_list::*;
+pub(crate) use keep_larger_zip::*;
+pub(crate) use keep_larger_primitive::*;
+pub(crate) use nan::*;
+pub(crate) use nullable::*;
+pub(crate) use output_ordering::*;
+pub(crate) use simplify::*;
 pub(crate) use transpose::*;
+pub(crate) use udf_options::*;
 pub(crate) use permutation::*;
 
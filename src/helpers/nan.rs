@@ -0,0 +1,179 @@
+use crate::NanOrdering;
+use datafusion::arrow::array::{make_array, Array, ArrayRef, ArrowPrimitiveType, AsArray, PrimitiveArray};
+use datafusion::arrow::buffer::{BooleanBuffer, NullBuffer};
+use datafusion::arrow::datatypes::{DataType, Float16Type, Float32Type, Float64Type};
+use datafusion::error::Result;
+use std::sync::Arc;
+
+/// Floating point arrow types, so NaN can be handled the same way for all of them
+trait FloatType: ArrowPrimitiveType {
+    const NAN: Self::Native;
+
+    fn is_nan(value: Self::Native) -> bool;
+}
+
+macro_rules! impl_float_type {
+    ($($arrow_type:ty),*) => {
+        $(
+            impl FloatType for $arrow_type {
+                const NAN: Self::Native = <Self as ArrowPrimitiveType>::Native::NAN;
+
+                fn is_nan(value: Self::Native) -> bool {
+                    value.is_nan()
+                }
+            }
+        )*
+    };
+}
+
+impl_float_type!(Float16Type, Float32Type, Float64Type);
+
+/// Return true if the data type is floating point or contains floating point values (e.g. list of floats)
+pub(crate) fn contains_float(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => true,
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) | DataType::Map(field, _) => contains_float(field.data_type()),
+        DataType::Struct(fields) => fields.iter().any(|field| contains_float(field.data_type())),
+        DataType::Union(fields, _) => fields.iter().any(|(_, field)| contains_float(field.data_type())),
+        DataType::Dictionary(_, value_type) => contains_float(value_type),
+        _ => false,
+    }
+}
+
+/// Return bitmap where `nan[i]` is true if the value at `i` is NaN, `None` for non floating point arrays
+///
+/// Null slots are not masked, the caller should check for nulls first
+pub(crate) fn nan_mask(array: &dyn Array) -> Option<BooleanBuffer> {
+    fn mask<T: FloatType>(array: &dyn Array) -> BooleanBuffer {
+        let values = array.as_primitive::<T>().values();
+
+        BooleanBuffer::collect_bool(values.len(), |i| T::is_nan(values[i]))
+    }
+
+    match array.data_type() {
+        DataType::Float16 => Some(mask::<Float16Type>(array)),
+        DataType::Float32 => Some(mask::<Float32Type>(array)),
+        DataType::Float64 => Some(mask::<Float64Type>(array)),
+
+        // Dictionary of floats, map the mask of the dictionary values through the keys
+        DataType::Dictionary(_, value_type) if value_type.is_floating() => {
+            let dictionary = array.as_any_dictionary();
+            let values_nan = nan_mask(dictionary.values().as_ref())?;
+            let keys = dictionary.normalized_keys();
+
+            // Keys of null slots may be out of bounds
+            Some(BooleanBuffer::collect_bool(keys.len(), |i| keys[i] < values_nan.len() && values_nan.value(keys[i])))
+        }
+        _ => None,
+    }
+}
+
+/// Return the array with NaN items inside nested values (e.g. list items) normalized
+/// so they are ordered according to `nan_ordering` by the arrow comparator
+///
+/// - `NanOrdering::Greatest` and `NanOrdering::Propagate`: all NaN are replaced with positive NaN which is the greatest in total order
+/// - `NanOrdering::Skip`: NaN are replaced with null which is the smallest
+///
+/// Top level values are not modified as they are handled by the caller
+pub(crate) fn normalize_nested_nan(array: &ArrayRef, nan_ordering: NanOrdering) -> Result<ArrayRef> {
+    if !array.data_type().is_nested() || !contains_float(array.data_type()) {
+        return Ok(array.clone());
+    }
+
+    normalize_children_nan(array, nan_ordering)
+}
+
+fn normalize_children_nan(array: &ArrayRef, nan_ordering: NanOrdering) -> Result<ArrayRef> {
+    let data = array.to_data();
+
+    let child_data = data
+        .child_data()
+        .iter()
+        .map(|child| normalize_nan(&make_array(child.clone()), nan_ordering).map(|child| child.to_data()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let data = data.into_builder().child_data(child_data).build()?;
+
+    Ok(make_array(data))
+}
+
+fn normalize_nan(array: &ArrayRef, nan_ordering: NanOrdering) -> Result<ArrayRef> {
+    fn normalize<T: FloatType>(array: &ArrayRef, nan_ordering: NanOrdering) -> ArrayRef {
+        let array = array.as_primitive::<T>();
+
+        match nan_ordering {
+            NanOrdering::Greatest | NanOrdering::Propagate => {
+                Arc::new(array.unary::<_, T>(|value| if T::is_nan(value) { T::NAN } else { value }))
+            }
+            NanOrdering::Skip => {
+                let not_nan = NullBuffer::new(BooleanBuffer::collect_bool(array.len(), |i| !T::is_nan(array.value(i))));
+                let nulls = NullBuffer::union(array.nulls(), Some(&not_nan));
+
+                Arc::new(PrimitiveArray::<T>::new(array.values().clone(), nulls).with_data_type(array.data_type().clone()))
+            }
+        }
+    }
+
+    match array.data_type() {
+        DataType::Float16 => Ok(normalize::<Float16Type>(array, nan_ordering)),
+        DataType::Float32 => Ok(normalize::<Float32Type>(array, nan_ordering)),
+        DataType::Float64 => Ok(normalize::<Float64Type>(array, nan_ordering)),
+        data_type if contains_float(data_type) => normalize_children_nan(array, nan_ordering),
+        _ => Ok(array.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{DictionaryArray, Float32Array, Int32Array, ListArray};
+    use datafusion::arrow::datatypes::{Field, Int32Type};
+
+    #[test]
+    fn contains_float_test() {
+        assert!(contains_float(&DataType::Float32));
+        assert!(contains_float(&DataType::new_list(DataType::Float64, true)));
+        assert!(contains_float(&DataType::Struct(vec![Field::new("a", DataType::Int8, true), Field::new("b", DataType::Float16, true)].into())));
+
+        assert!(!contains_float(&DataType::Int32));
+        assert!(!contains_float(&DataType::new_list(DataType::Utf8, true)));
+    }
+
+    #[test]
+    fn nan_mask_test() {
+        let array = Float32Array::from(vec![Some(1.0), Some(f32::NAN), None, Some(-f32::NAN), Some(f32::INFINITY)]);
+
+        assert_eq!(nan_mask(&array), Some(BooleanBuffer::from(vec![false, true, false, true, false])));
+        assert_eq!(nan_mask(&Int32Array::from(vec![1, 2])), None);
+
+        let dictionary = DictionaryArray::new(
+            Int32Array::from(vec![Some(1), Some(0), None, Some(1)]),
+            Arc::new(Float32Array::from(vec![1.0, f32::NAN])),
+        );
+
+        assert_eq!(nan_mask(&dictionary), Some(BooleanBuffer::from(vec![true, false, false, true])));
+    }
+
+    #[test]
+    fn normalize_nested_nan_skip_test() {
+        let array: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Float32Type, _, _>(vec![
+            Some(vec![Some(1.0), Some(f32::NAN)]),
+            None,
+        ]));
+
+        let normalized = normalize_nested_nan(&array, NanOrdering::Skip).unwrap();
+
+        assert_eq!(
+            normalized.as_list::<i32>().clone(),
+            ListArray::from_iter_primitive::<Float32Type, _, _>(vec![
+                Some(vec![Some(1.0), None]),
+                None,
+            ])
+        );
+
+        // Not floating point values are not modified
+        let array: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)])]));
+
+        assert_eq!(&normalize_nested_nan(&array, NanOrdering::Skip).unwrap(), &array);
+    }
+}
@@ -0,0 +1,12 @@
+use crate::NullHandling;
+
+/// Return whether the largest (or smallest) value can be NULL given whether each of the arguments can be NULL
+///
+/// When skipping nulls the output is NULL only when all the arguments are NULL, so it is nullable only if all the arguments are.
+/// When propagating nulls any NULL argument makes the output NULL, so it is nullable if any of the arguments is.
+pub(crate) fn keep_larger_nullable(args_nullable: &[bool], null_handling: NullHandling) -> bool {
+    match null_handling {
+        NullHandling::Skip => args_nullable.iter().all(|nullable| *nullable),
+        NullHandling::Propagate => args_nullable.iter().any(|nullable| *nullable),
+    }
+}
@@ -0,0 +1,50 @@
+use crate::helpers::{contains_float, CompareOptions, KeepOrder};
+use crate::NullHandling;
+use datafusion::arrow::compute::SortOptions;
+use datafusion_expr::sort_properties::{ExprProperties, SortProperties};
+
+/// Return the ordering of the largest (or smallest, depending on `options`) value of the inputs
+///
+/// The output is ordered only if all the non-constant inputs are ordered the same way (constants are ignored),
+/// and that order agrees with how NULL values are compared, otherwise it is unordered.
+///
+/// Floating point inputs are always unordered, as the sort puts -NaN first and NaN last
+/// while all the NaN orderings compare them the same, so sorted inputs can produce unsorted output
+/// (e.g. `least([-NaN, 0], [5, 6])` is `[5, 0]` when NaN is the greatest value)
+pub(crate) fn keep_larger_output_ordering(input: &[ExprProperties], options: CompareOptions) -> SortProperties {
+    let mut ordering = SortProperties::Singleton;
+
+    for properties in input {
+        ordering = match (ordering, properties.sort_properties) {
+            (ordering, SortProperties::Singleton) => ordering,
+            (SortProperties::Singleton, ordering) => ordering,
+            (SortProperties::Ordered(lhs), SortProperties::Ordered(rhs)) if lhs == rhs => ordering,
+            _ => return SortProperties::Unordered,
+        };
+    }
+
+    let has_float = input.iter().any(|properties| contains_float(&properties.range.data_type()));
+
+    match ordering {
+        SortProperties::Ordered(_) if has_float => SortProperties::Unordered,
+        SortProperties::Ordered(sort_options) if !is_order_preserved(sort_options, options) => SortProperties::Unordered,
+        ordering => ordering,
+    }
+}
+
+/// Whether keeping the larger value of inputs that are all sorted with `sort_options` produces sorted output
+fn is_order_preserved(sort_options: SortOptions, options: CompareOptions) -> bool {
+    // Whether the sort puts the NULL values where the smallest values are
+    let nulls_are_smallest = sort_options.nulls_first != sort_options.descending;
+
+    match options.null_handling {
+        // NULL is kept only when the value at the same row is NULL in all the inputs
+        // so it must be sorted as the value that is never kept
+        NullHandling::Skip => match options.order {
+            KeepOrder::Larger => nulls_are_smallest,
+            KeepOrder::Smaller => !nulls_are_smallest,
+        },
+        // A NULL in any input makes the output NULL, so the NULL rows are at the same end as in the inputs
+        NullHandling::Propagate => true,
+    }
+}
@@ -0,0 +1,161 @@
+use crate::helpers::{find_coerced_type, keep_larger_scalar, CompareOptions};
+use crate::NullHandling;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::ScalarValue;
+use datafusion_expr::expr::ScalarFunction;
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
+use datafusion_expr::{cast, lit, Expr, ScalarUDF, ScalarUDFImpl};
+use std::sync::Arc;
+
+/// Simplify a call to `udf` (a greatest like function) with the given arguments
+///
+/// 1. Nested calls to an equal function are flattened into the current call
+/// 2. `NULL` literals are removed when skipping nulls, or the whole call is `NULL` when propagating them
+/// 3. All the literal arguments are collapsed into the largest one (or the smallest one, depending on `options`)
+/// 4. Identical arguments are deduplicated (unless they are volatile)
+/// 5. A call that is left with a single argument is replaced by that argument
+///
+/// The simplified expression is casted to the original return type when the remaining arguments have a different common type
+pub(crate) fn simplify_args<F: ScalarUDFImpl + Clone + 'static>(
+    udf: &F,
+    args: Vec<Expr>,
+    info: &dyn SimplifyInfo,
+    options: CompareOptions,
+) -> Result<ExprSimplifyResult> {
+    let arg_types = args
+        .iter()
+        .map(|arg| info.get_data_type(arg))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Leave invalid calls as is, so the error is reported when planning them
+    let Ok(return_type) = find_coerced_type(udf.name(), &arg_types) else {
+        return Ok(ExprSimplifyResult::Original(args));
+    };
+
+    let mut simplified = Vec::with_capacity(args.len());
+
+    for arg in &args {
+        flatten_arg(udf, arg, info, &return_type, &mut simplified)?;
+    }
+
+    match options.null_handling {
+        NullHandling::Skip => simplified.retain(|arg| !is_null_literal(arg)),
+        NullHandling::Propagate => {
+            if simplified.iter().any(is_null_literal) {
+                return Ok(ExprSimplifyResult::Simplified(lit(ScalarValue::try_from(&return_type)?)));
+            }
+        }
+    }
+
+    let simplified = dedup_args(fold_literals(simplified, options)?)?;
+
+    if simplified == args {
+        return Ok(ExprSimplifyResult::Original(args));
+    }
+
+    let expr = match <[Expr; 1]>::try_from(simplified) {
+        Ok([arg]) if info.get_data_type(&arg)? == return_type => arg,
+        Ok([arg]) => cast(arg, return_type),
+
+        // All the arguments were NULL literals
+        Err(simplified) if simplified.is_empty() => lit(ScalarValue::try_from(&return_type)?),
+
+        Err(simplified) => {
+            let simplified_types = simplified
+                .iter()
+                .map(|arg| info.get_data_type(arg))
+                .collect::<Result<Vec<_>>>()?;
+
+            let call = Expr::ScalarFunction(ScalarFunction::new_udf(
+                Arc::new(ScalarUDF::new_from_impl(udf.clone())),
+                simplified,
+            ));
+
+            // The removed arguments may have set the return type, so the call is casted back to keep the same type
+            match find_coerced_type(udf.name(), &simplified_types) {
+                Ok(simplified_type) if simplified_type == return_type => call,
+                _ => cast(call, return_type),
+            }
+        }
+    };
+
+    Ok(ExprSimplifyResult::Simplified(expr))
+}
+
+/// Push `arg` into `args`, replacing nested calls to `udf` with their own arguments
+fn flatten_arg<F: ScalarUDFImpl>(
+    udf: &F,
+    arg: &Expr,
+    info: &dyn SimplifyInfo,
+    return_type: &DataType,
+    args: &mut Vec<Expr>,
+) -> Result<()> {
+    match arg {
+        // Only flatten when the nested call was not casted, otherwise its arguments can have a different type
+        Expr::ScalarFunction(ScalarFunction { func, args: nested_args })
+        if func.inner().equals(udf) && info.get_data_type(arg)? == *return_type => {
+            for nested_arg in nested_args {
+                flatten_arg(udf, nested_arg, info, return_type, args)?;
+            }
+        }
+        _ => args.push(arg.clone()),
+    }
+
+    Ok(())
+}
+
+fn is_null_literal(arg: &Expr) -> bool {
+    matches!(arg, Expr::Literal(value) if value.is_null())
+}
+
+/// Collapse all the literal arguments into the one that would be kept, placed where the first literal was
+fn fold_literals(args: Vec<Expr>, options: CompareOptions) -> Result<Vec<Expr>> {
+    let literals = args
+        .iter()
+        .filter_map(|arg| match arg {
+            Expr::Literal(value) => Some(value),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Literals of different types can't be compared, this should not happen after type coercion
+    if literals.len() < 2 || literals.iter().any(|value| value.data_type() != literals[0].data_type()) {
+        return Ok(args);
+    }
+
+    let mut largest = literals[0].clone();
+
+    for value in &literals[1..] {
+        largest = keep_larger_scalar(&largest, value, options)?;
+    }
+
+    let mut folded = false;
+
+    Ok(
+        args
+            .into_iter()
+            .filter_map(|arg| match arg {
+                Expr::Literal(_) if folded => None,
+                Expr::Literal(_) => {
+                    folded = true;
+                    Some(Expr::Literal(largest.clone()))
+                }
+                arg => Some(arg),
+            })
+            .collect()
+    )
+}
+
+/// Remove repeated arguments, volatile arguments are kept as each evaluation can return a different value
+fn dedup_args(args: Vec<Expr>) -> Result<Vec<Expr>> {
+    let mut deduped: Vec<Expr> = Vec::with_capacity(args.len());
+
+    for arg in args {
+        if arg.is_volatile()? || !deduped.contains(&arg) {
+            deduped.push(arg);
+        }
+    }
+
+    Ok(deduped)
+}
@@ -0,0 +1,81 @@
+use datafusion::logical_expr::Volatility;
+use datafusion_expr::{ScalarUDFImpl, Signature};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// The signature of all the functions, each function coerce its own arguments
+pub(crate) fn user_defined_signature() -> Signature {
+    Signature::user_defined(
+        // Deterministic
+        Volatility::Immutable
+    )
+}
+
+/// Return true if `other` is the same function as `udf` with the same options
+///
+/// Instances with different options (e.g. null handling) must not be considered equal,
+/// otherwise DataFusion can replace one with the other when deduplicating expressions
+pub(crate) fn udf_equals<T: ScalarUDFImpl + PartialEq + 'static>(udf: &T, other: &dyn ScalarUDFImpl) -> bool {
+    other.as_any().downcast_ref::<T>().is_some_and(|other| udf == other)
+}
+
+/// Return the hash of `udf` including its options, consistent with [`udf_equals`]
+pub(crate) fn udf_hash<T: ScalarUDFImpl + Hash>(udf: &T) -> u64 {
+    let hasher = &mut DefaultHasher::new();
+    udf.name().hash(hasher);
+    udf.hash(hasher);
+    hasher.finish()
+}
+
+/// Implement the public methods to configure the `options: CompareOptions` field of a function,
+/// `$values` is how the compared values are called in the docs (e.g. "arguments")
+macro_rules! impl_compare_options {
+    ($udf:ty, $values:literal) => {
+        impl $udf {
+            #[doc = concat!("Set how NULL ", $values, " are handled, by default NULL ", $values, " are skipped")]
+            pub fn with_null_handling(mut self, null_handling: $crate::NullHandling) -> Self {
+                self.options.null_handling = null_handling;
+                self
+            }
+
+            #[doc = concat!("Return how NULL ", $values, " are handled")]
+            pub fn null_handling(&self) -> $crate::NullHandling {
+                self.options.null_handling
+            }
+
+            #[doc = concat!("Set how NaN floating point ", $values, " are ordered, by default NaN is greater than any other value")]
+            pub fn with_nan_ordering(mut self, nan_ordering: $crate::NanOrdering) -> Self {
+                self.options.nan_ordering = nan_ordering;
+                self
+            }
+
+            #[doc = concat!("Return how NaN floating point ", $values, " are ordered")]
+            pub fn nan_ordering(&self) -> $crate::NanOrdering {
+                self.options.nan_ordering
+            }
+        }
+    };
+}
+
+pub(crate) use impl_compare_options;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GreatestIndexUdf, GreatestUdf, LeastUdf, LengthMismatch, NanOrdering, NullHandling, ZipGreatestUdf};
+
+    #[test]
+    fn equal_only_with_same_options() {
+        let greatest = GreatestUdf::new();
+
+        assert!(udf_equals(&greatest, &GreatestUdf::new()));
+        assert_eq!(udf_hash(&greatest), udf_hash(&GreatestUdf::new()));
+
+        assert!(!udf_equals(&greatest, &GreatestUdf::new().with_null_handling(NullHandling::Propagate)));
+        assert!(!udf_equals(&greatest, &GreatestUdf::new().with_nan_ordering(NanOrdering::Skip)));
+        assert!(!udf_equals(&greatest, &LeastUdf::new()));
+        assert!(!udf_equals(&greatest, &GreatestIndexUdf::new()));
+
+        // Options of a single function are compared as well
+        assert!(!udf_equals(&ZipGreatestUdf::new(), &ZipGreatestUdf::new().with_length_mismatch(LengthMismatch::Error)));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::helpers::{check_comparable, find_coerced_type, impl_compare_options, keep_larger_columnar, keep_larger_nullable, keep_larger_output_ordering, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::plan_err;
+use datafusion_expr::sort_properties::{ExprProperties, SortProperties};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use std::any::Any;
+
+/// Return the smallest value across all the arguments, by default nulls are skipped.
+///
+/// This is the companion of [`GreatestUdf`](crate::GreatestUdf) and share the same
+/// comparison, coercion and null semantics, only with the inverted order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeastUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+    options: CompareOptions,
+}
+
+impl LeastUdf {
+    /// Create a new instance of the `LeastUdf` struct
+    pub fn new() -> Self {
+        Self {
+            signature: user_defined_signature(),
+            aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Smaller, NullHandling::default(), NanOrdering::default()),
+        }
+    }
+
+    /// Return whether `least` can return NULL given whether each of its arguments can be NULL
+    ///
+    /// When NULL arguments are skipped the result is non-nullable if any of the arguments is non-nullable,
+    /// and when they are propagated it is non-nullable only if all the arguments are.
+    ///
+    /// DataFusion 41 always reports scalar functions as nullable and has no hook for a function to override it,
+    /// so use this to mark the output field as non-nullable where the schema is built (e.g. before writing to Parquet)
+    pub fn nullable(&self, args_nullable: &[bool]) -> bool {
+        keep_larger_nullable(args_nullable, self.options.null_handling)
+    }
+}
+
+impl_compare_options!(LeastUdf, "arguments");
+
+impl Default for LeastUdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for LeastUdf {
+    /// We implement as_any so that we can downcast the ScalarUDFImpl trait object
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Return the name of this function
+    fn name(&self) -> &str {
+        "least"
+    }
+
+    /// Return the "signature" of this function -- namely what types of arguments it will take
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The return type is the common type of all the arguments
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        find_coerced_type(self.name(), arg_types)
+    }
+
+    /// Calculate the smallest value at each row, same as `greatest` but with the inverted order
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        keep_larger_columnar(args, self.options)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// The result is ordered only if all the non-constant arguments are ordered the same way
+    /// and NULL and NaN values are sorted where they would be compared
+    fn output_ordering(&self, input: &[ExprProperties]) -> Result<SortProperties> {
+        Ok(keep_larger_output_ordering(input, self.options))
+    }
+
+    /// What types can this function coerce its arguments to?
+    fn coerce_types(&self, input_types: &[DataType]) -> Result<Vec<DataType>> {
+        // make sure that the input types has at least 2 elements, same as greatest
+        if input_types.len() < 2 {
+            return plan_err!(
+                "least was called with {} arguments. It requires at least 2.",
+                input_types.len()
+            );
+        }
+
+        let coerced_type = find_coerced_type(self.name(), input_types)?;
+
+        // Fail when planning if the values can't be ordered, instead of when executing
+        check_comparable(self.name(), &coerced_type)?;
+
+        Ok(vec![coerced_type; input_types.len()])
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
+    }
+
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}
@@ -1,4 +1,12 @@
 mod greatest;
+mod greatest_by;
+mod greatest_index;
+mod least;
+mod list_greatest;
+mod options;
+mod register;
+mod rewrite_greatest_predicate;
+mod zip_greatest;
 
 #[cfg(test)]
 mod tests;
@@ -6,5 +14,16 @@ mod traits;
 mod helpers;
 
 pub use greatest::GreatestUdf;
+pub use greatest_by::GreatestByUdf;
+pub use greatest_index::GreatestIndexUdf;
+pub use least::LeastUdf;
+pub use list_greatest::ListGreatestUdf;
+pub use options::{LengthMismatch, NanOrdering, NullHandling};
+pub use register::{functions, register_all, register_all_with_registry};
+pub use rewrite_greatest_predicate::RewriteGreatestPredicate;
+pub use zip_greatest::ZipGreatestUdf;
+
+#[cfg(feature = "bench")]
+pub use helpers::keep_larger_pairwise;
 
 pub(crate) use helpers::*;
@@ -0,0 +1,112 @@
+use crate::helpers::{check_comparable, impl_compare_options, keep_larger_in_list, list_item_type, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::Result;
+use datafusion_common::{exec_err, plan_err, ScalarValue};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use std::any::Any;
+
+/// Return the greatest item of each list, or NULL when the list is NULL or empty.
+///
+/// This is similar to Spark `array_max` and supports `List`, `LargeList` and `FixedSizeList` arguments.
+/// Items are compared the same way as the arguments of [`GreatestUdf`](crate::GreatestUdf),
+/// with the same null and NaN semantics, so `list_greatest([a, b])` is the same as `greatest(a, b)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListGreatestUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+    options: CompareOptions,
+}
+
+impl ListGreatestUdf {
+    /// Create a new instance of the `ListGreatestUdf` struct
+    pub fn new() -> Self {
+        Self {
+            signature: user_defined_signature(),
+            aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Larger, NullHandling::default(), NanOrdering::default()),
+        }
+    }
+}
+
+impl_compare_options!(ListGreatestUdf, "items");
+
+impl Default for ListGreatestUdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for ListGreatestUdf {
+    /// We implement as_any so that we can downcast the ScalarUDFImpl trait object
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Return the name of this function
+    fn name(&self) -> &str {
+        "list_greatest"
+    }
+
+    /// Return the "signature" of this function -- namely what types of arguments it will take
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The return type is the type of the list items
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types.first().and_then(list_item_type) {
+            Some(item_type) => Ok(item_type.clone()),
+            None => plan_err!("list_greatest expects a list argument, got {:?}", arg_types),
+        }
+    }
+
+    /// Calculate the greatest item of each list
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        // DataFusion has arranged for the correct inputs to be passed to this
+        // function, but we check again to make sure, as the function can also be invoked directly
+        let [arg] = args else {
+            return exec_err!("Expected 1 argument, got {}", args.len());
+        };
+
+        match arg {
+            ColumnarValue::Array(array) => Ok(ColumnarValue::Array(keep_larger_in_list(array.as_ref(), self.options)?)),
+            ColumnarValue::Scalar(scalar) => {
+                let largest = keep_larger_in_list(scalar.to_array()?.as_ref(), self.options)?;
+
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(largest.as_ref(), 0)?))
+            }
+        }
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// The argument is not coerced, only checked to be a list of items that can be ordered
+    fn coerce_types(&self, input_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [input_type] = input_types else {
+            return plan_err!(
+                "list_greatest was called with {} arguments. It requires exactly 1.",
+                input_types.len()
+            );
+        };
+
+        let Some(item_type) = list_item_type(input_type) else {
+            return plan_err!("list_greatest expects a list argument, got {}", input_type);
+        };
+
+        // Fail when planning if the items can't be ordered, instead of when executing
+        check_comparable(self.name(), item_type)?;
+
+        Ok(vec![input_type.clone()])
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
+    }
+
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}
@@ -0,0 +1,47 @@
+/// How NULL arguments are handled when looking for the greatest (or least) value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NullHandling {
+    /// NULL arguments are skipped, the result is NULL only when all the arguments are NULL.
+    ///
+    /// This is the behavior of Spark and Postgres, for example `greatest(1, NULL)` is `1`
+    #[default]
+    Skip,
+
+    /// Any NULL argument makes the result NULL.
+    ///
+    /// This is the behavior of MySQL and Oracle, for example `greatest(1, NULL)` is `NULL`
+    Propagate,
+}
+
+/// How NaN floating point arguments are ordered when looking for the greatest (or least) value
+///
+/// Negative and positive zero are not affected, `-0.0` is always smaller than `+0.0`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NanOrdering {
+    /// NaN is greater than any other value, including infinity, and all NaN values are equal.
+    ///
+    /// This is the behavior of Spark and Postgres, for example `greatest(1.0, 'NaN')` is `NaN` and `least(1.0, 'NaN')` is `1.0`
+    #[default]
+    Greatest,
+
+    /// NaN arguments are skipped like NULL, NaN is returned only when there is no other non-null value
+    Skip,
+
+    /// Any NaN argument makes the result NaN (NULL arguments are handled first, according to the `NullHandling`).
+    ///
+    /// NaN items inside nested values (e.g. lists) are ordered as the greatest value
+    Propagate,
+}
+
+/// How lists of different lengths are compared item by item by `zip_greatest`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LengthMismatch {
+    /// Shorter lists are padded with NULL items, so the result is as long as the longest list.
+    ///
+    /// The padded items are handled like any other NULL item, according to the `NullHandling`
+    #[default]
+    PadWithNull,
+
+    /// Lists of different lengths in the same row fail the query
+    Error,
+}
@@ -0,0 +1,35 @@
+use crate::{GreatestByUdf, GreatestIndexUdf, GreatestUdf, LeastUdf, ListGreatestUdf, ZipGreatestUdf};
+use datafusion::error::Result;
+use datafusion::execution::FunctionRegistry;
+use datafusion::prelude::SessionContext;
+use datafusion_expr::ScalarUDF;
+use std::sync::Arc;
+
+/// Return all the functions this crate provides
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![
+        Arc::new(ScalarUDF::from(GreatestUdf::new())),
+        Arc::new(ScalarUDF::from(LeastUdf::new())),
+        Arc::new(ScalarUDF::from(GreatestIndexUdf::new())),
+        Arc::new(ScalarUDF::from(ListGreatestUdf::new())),
+        Arc::new(ScalarUDF::from(ZipGreatestUdf::new())),
+        Arc::new(ScalarUDF::from(GreatestByUdf::new())),
+    ]
+}
+
+/// Register all the functions this crate provides on the session context
+/// so they can be invoked by name and from SQL
+pub fn register_all(ctx: &SessionContext) {
+    for udf in functions() {
+        ctx.register_udf(udf.as_ref().clone());
+    }
+}
+
+/// Register all the functions this crate provides on any [`FunctionRegistry`], for example `SessionState`
+pub fn register_all_with_registry(registry: &mut dyn FunctionRegistry) -> Result<()> {
+    for udf in functions() {
+        registry.register_udf(udf)?;
+    }
+
+    Ok(())
+}
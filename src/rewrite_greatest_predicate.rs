@@ -0,0 +1,202 @@
+use crate::helpers::contains_float;
+use crate::{GreatestUdf, NullHandling};
+use datafusion::error::Result;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::DFSchema;
+use datafusion_expr::expr::ScalarFunction;
+use datafusion_expr::logical_plan::Filter;
+use datafusion_expr::utils::{conjunction, disjunction};
+use datafusion_expr::{BinaryExpr, Expr, ExprSchemable, LogicalPlan, Operator};
+use datafusion_optimizer::optimizer::ApplyOrder;
+use datafusion_optimizer::{OptimizerConfig, OptimizerRule};
+
+/// Optimizer rule that rewrites filter predicates on `greatest` into predicates on its arguments,
+/// so they can be pushed down to the table scans.
+///
+/// | Predicate                  | Rewritten to                                |
+/// |----------------------------|---------------------------------------------|
+/// | `greatest(a, b) > c`       | `a > c OR b > c`                            |
+/// | `greatest(a, b) >= c`      | `a >= c OR b >= c`                          |
+/// | `greatest(a, b) < c`       | `a < c AND b < c`                           |
+/// | `greatest(a, b) <= c`      | `a <= c AND b <= c`                         |
+/// | `greatest(a, b) = c`       | `a <= c AND b <= c AND (a = c OR b = c)`    |
+///
+/// Nullable arguments are guarded with `IS NULL` / `IS NOT NULL` checks according to the function `NullHandling`.
+///
+/// Only predicates that are combined with `AND` and `OR` are rewritten, as a NULL predicate result is treated
+/// like false there, which is not the case when negated.
+/// Calls with floating point arguments are not rewritten, as `greatest` orders NaN differently than the comparison operators.
+///
+/// Register it with `SessionStateBuilder::with_optimizer_rule` or `SessionContext::add_optimizer_rule`
+#[derive(Debug, Default)]
+pub struct RewriteGreatestPredicate {}
+
+impl RewriteGreatestPredicate {
+    /// Create a new instance of the `RewriteGreatestPredicate` rule
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for RewriteGreatestPredicate {
+    fn name(&self) -> &str {
+        "rewrite_greatest_predicate"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(&self, plan: LogicalPlan, _config: &dyn OptimizerConfig) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Filter(filter) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+
+        match rewrite_predicate(&filter.predicate, filter.input.schema())? {
+            Some(predicate) => Ok(Transformed::yes(LogicalPlan::Filter(Filter::try_new(predicate, filter.input)?))),
+            None => Ok(Transformed::no(LogicalPlan::Filter(filter))),
+        }
+    }
+}
+
+/// Return the rewritten predicate, or `None` if nothing was rewritten
+fn rewrite_predicate(predicate: &Expr, schema: &DFSchema) -> Result<Option<Expr>> {
+    let Expr::BinaryExpr(BinaryExpr { left, op, right }) = predicate else {
+        return Ok(None);
+    };
+
+    match op {
+        Operator::And | Operator::Or => {
+            let new_left = rewrite_predicate(left, schema)?;
+            let new_right = rewrite_predicate(right, schema)?;
+
+            if new_left.is_none() && new_right.is_none() {
+                return Ok(None);
+            }
+
+            Ok(Some(Expr::BinaryExpr(BinaryExpr::new(
+                Box::new(new_left.unwrap_or_else(|| left.as_ref().clone())),
+                *op,
+                Box::new(new_right.unwrap_or_else(|| right.as_ref().clone())),
+            ))))
+        }
+        _ => {
+            if let Some((args, greatest)) = as_greatest_call(left) {
+                rewrite_comparison(args, greatest, *op, right, schema)
+            } else if let (Some((args, greatest)), Some(op)) = (as_greatest_call(right), op.swap()) {
+                rewrite_comparison(args, greatest, op, left, schema)
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn as_greatest_call(expr: &Expr) -> Option<(&[Expr], &GreatestUdf)> {
+    let Expr::ScalarFunction(ScalarFunction { func, args }) = expr else {
+        return None;
+    };
+
+    func
+        .inner()
+        .as_any()
+        .downcast_ref::<GreatestUdf>()
+        .map(|greatest| (args.as_slice(), greatest))
+}
+
+/// Rewrite `greatest(args) op value` into predicates on each of the arguments
+fn rewrite_comparison(args: &[Expr], greatest: &GreatestUdf, op: Operator, value: &Expr, schema: &DFSchema) -> Result<Option<Expr>> {
+    if !matches!(op, Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq | Operator::Eq) || args.is_empty() {
+        return Ok(None);
+    }
+
+    // The arguments and the value are evaluated more than once after the rewrite
+    if value.is_volatile()? {
+        return Ok(None);
+    }
+
+    for arg in args {
+        if arg.is_volatile()? || contains_float(&arg.get_type(schema)?) {
+            return Ok(None);
+        }
+    }
+
+    let nullable = args
+        .iter()
+        .map(|arg| arg.nullable(schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    let predicate = match op {
+        Operator::Gt | Operator::GtEq => any_arg(args, &nullable, greatest, op, value),
+        Operator::Lt | Operator::LtEq => all_args(args, &nullable, greatest, op, value),
+        Operator::Eq => conjunction(
+            all_args(args, &nullable, greatest, Operator::LtEq, value)
+                .into_iter()
+                .chain(any_arg(args, &nullable, greatest, Operator::Eq, value))
+        ),
+        _ => None,
+    };
+
+    Ok(predicate)
+}
+
+/// Predicate that is true when the largest argument satisfies `op value` given at least one argument does
+fn any_arg(args: &[Expr], nullable: &[bool], greatest: &GreatestUdf, op: Operator, value: &Expr) -> Option<Expr> {
+    let any = disjunction(args.iter().map(|arg| compare(arg, op, value)))?;
+
+    match greatest.null_handling() {
+        NullHandling::Skip => Some(any),
+
+        // A NULL argument makes the result NULL even when another argument satisfies the predicate
+        NullHandling::Propagate => conjunction(
+            std::iter::once(any).chain(not_null_args(args, nullable))
+        ),
+    }
+}
+
+/// Predicate that is true when the largest argument satisfies `op value` given all the arguments do
+fn all_args(args: &[Expr], nullable: &[bool], greatest: &GreatestUdf, op: Operator, value: &Expr) -> Option<Expr> {
+    match greatest.null_handling() {
+        // A NULL argument makes the comparison NULL, as it makes the result NULL
+        NullHandling::Propagate => conjunction(args.iter().map(|arg| compare(arg, op, value))),
+
+        // NULL arguments are skipped, but the result is NULL when all of them are NULL
+        NullHandling::Skip => {
+            let each = args
+                .iter()
+                .zip(nullable)
+                .map(|(arg, nullable)| {
+                    if *nullable {
+                        compare(arg, op, value).or(arg.clone().is_null())
+                    } else {
+                        compare(arg, op, value)
+                    }
+                });
+
+            // Only needed when the result can be NULL
+            let some_not_null = if greatest.nullable(nullable) {
+                disjunction(args.iter().map(|arg| arg.clone().is_not_null()))
+            } else {
+                None
+            };
+
+            conjunction(each.chain(some_not_null))
+        }
+    }
+}
+
+fn not_null_args<'a>(args: &'a [Expr], nullable: &'a [bool]) -> impl Iterator<Item=Expr> + 'a {
+    args
+        .iter()
+        .zip(nullable)
+        .filter(|(_, nullable)| **nullable)
+        .map(|(arg, _)| arg.clone().is_not_null())
+}
+
+fn compare(arg: &Expr, op: Operator, value: &Expr) -> Expr {
+    Expr::BinaryExpr(BinaryExpr::new(Box::new(arg.clone()), op, Box::new(value.clone())))
+}
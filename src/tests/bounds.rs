@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod bounds_tests {
+    //! These tests check that interval analysis narrows the columns bounds through greatest
+
+    use crate::tests::utils::create_context;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::physical_expr::analysis::{analyze, AnalysisContext, ExprBoundaries};
+    use datafusion::physical_expr::expressions::Column;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::DFSchema;
+    use datafusion_expr::interval_arithmetic::Interval;
+    use datafusion_expr::{col, lit, Expr};
+
+    fn analyze_filter(expr: Expr, a: Interval, b: Interval) -> AnalysisContext {
+        let (ctx, _) = create_context();
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]);
+        let df_schema = DFSchema::try_from(schema.clone()).unwrap();
+
+        let physical_expr = ctx.create_physical_expr(expr, &df_schema).unwrap();
+
+        let boundaries = [("a", a), ("b", b)]
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, interval))| ExprBoundaries {
+                column: Column::new(name, index),
+                interval,
+                distinct_count: Precision::Absent,
+            })
+            .collect();
+
+        analyze(&physical_expr, AnalysisContext::new(boundaries), &schema).unwrap()
+    }
+
+    fn boundaries(context: AnalysisContext) -> Vec<Interval> {
+        context
+            .boundaries
+            .into_iter()
+            .map(|boundary| boundary.interval)
+            .collect()
+    }
+
+    #[test]
+    fn upper_bound_is_pushed_to_all_arguments() {
+        let (_, greatest) = create_context();
+
+        let context = analyze_filter(
+            greatest.call(vec![col("a"), col("b")]).lt_eq(lit(10)),
+            Interval::make(Some(0), Some(20)).unwrap(),
+            Interval::make(Some(5), Some(30)).unwrap(),
+        );
+
+        assert_eq!(
+            boundaries(context),
+            vec![
+                Interval::make(Some(0), Some(10)).unwrap(),
+                Interval::make(Some(5), Some(10)).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_bound_is_pushed_to_the_only_argument_that_can_reach_it() {
+        let (_, greatest) = create_context();
+
+        let context = analyze_filter(
+            greatest.call(vec![col("a"), col("b")]).gt_eq(lit(10)),
+            Interval::make(Some(0), Some(20)).unwrap(),
+            Interval::make(Some(5), Some(8)).unwrap(),
+        );
+
+        assert_eq!(
+            boundaries(context),
+            vec![
+                Interval::make(Some(10), Some(20)).unwrap(),
+                Interval::make(Some(5), Some(8)).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_bound_is_detected() {
+        let (_, greatest) = create_context();
+
+        let context = analyze_filter(
+            greatest.call(vec![col("a"), col("b")]).gt(lit(50)),
+            Interval::make(Some(0), Some(20)).unwrap(),
+            Interval::make(Some(5), Some(30)).unwrap(),
+        );
+
+        // Neither of the arguments can be above 50
+        assert_eq!(context.selectivity, Some(0.0));
+    }
+}
@@ -4,7 +4,11 @@ mod coerce_tests {
     use crate::helpers::Permutation;
     use crate::tests::utils::{create_context, create_empty_data_frame, get_combined_results, parse_many_primitives_columns};
     use crate::vec_with_lit;
-    use datafusion::arrow::datatypes::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type};
+    use crate::helpers::find_coerced_type;
+    use datafusion::arrow::array::AsArray;
+    use datafusion::arrow::datatypes::{i256, DataType, Decimal128Type, Decimal256Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, TimeUnit, TimestampNanosecondType};
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::lit;
 
     #[tokio::test]
     async fn coerce_types() {
@@ -58,23 +62,23 @@ mod coerce_tests {
 
 
     #[tokio::test]
-    async fn have_incompatible_type() {
+    async fn signed_and_unsigned_are_widened() {
         let (ctx, greatest) = create_context();
 
         let df = create_empty_data_frame(&ctx, true).unwrap();
 
         // Testing permutation of all types to make sure that the order of the arguments does not matter
 
-        // i8 and u8 are incompatible but i16 contain both
-        let i8_and_u8 = vec_with_lit![1i8, 2u8, 3i16].permutation(3);
-        // i16 and u16 are incompatible but i32 contain both
-        let i16_and_u16 = vec_with_lit![1i16, 2u16, 3i32].permutation(3);
-        // i32 and u32 are incompatible but i64 contain both
-        let i32_and_u32 = vec_with_lit![1i32, 2u32, 3i64].permutation(3);
-        // i64 and u64 are incompatible but f32 contain both
-        let i64_and_u64_with_f32 = vec_with_lit![1i64, 2u64, 3f32].permutation(3);
-        // i64 and u64 are incompatible but f64 contain both
-        let i64_and_u64_with_f64 = vec_with_lit![1i64, 2u64, 3f64].permutation(3);
+        // i8 and u8 are widened to i16 that contain both, even when none of the arguments is i16
+        let i8_and_u8 = vec_with_lit![1i8, 2u8].permutation(2);
+        // i16 and u16 are widened to i32 that contain both
+        let i16_and_u16 = vec_with_lit![1i16, 2u16].permutation(2);
+        // i32 and u32 are widened to i64 that contain both
+        let i32_and_u32 = vec_with_lit![1i32, 2u32].permutation(2);
+        // i64 and u64 are widened to the floating point argument
+        let i64_and_u64_with_f32 = vec_with_lit![1i64, 2u64, 0f32].permutation(3);
+        // i64 and u64 are widened to the floating point argument
+        let i64_and_u64_with_f64 = vec_with_lit![1i64, 2u64, 0f64].permutation(3);
         let greatest_calls = vec![
             i8_and_u8.clone(),
             i16_and_u16.clone(),
@@ -88,23 +92,253 @@ mod coerce_tests {
         let results = get_combined_results(df).await.unwrap();
         let columns: Vec<_> = results.columns().iter().collect();
 
+        // The unsigned value is the largest in all the calls
         let (i16_results, columns) = columns.split_at(i8_and_u8.len());
-        parse_many_primitives_columns::<Int16Type>(i16_results);
+        assert!(parse_many_primitives_columns::<Int16Type>(i16_results).iter().all(|values| values == &[Some(2)]));
 
         let (i32_results, columns) = columns.split_at(i16_and_u16.len());
-        parse_many_primitives_columns::<Int32Type>(i32_results);
+        assert!(parse_many_primitives_columns::<Int32Type>(i32_results).iter().all(|values| values == &[Some(2)]));
 
         let (i64_results, columns) = columns.split_at(i32_and_u32.len());
-        parse_many_primitives_columns::<Int64Type>(i64_results);
+        assert!(parse_many_primitives_columns::<Int64Type>(i64_results).iter().all(|values| values == &[Some(2)]));
 
         let (f32_results, columns) = columns.split_at(i64_and_u64_with_f32.len());
-        parse_many_primitives_columns::<Float32Type>(f32_results);
+        assert!(parse_many_primitives_columns::<Float32Type>(f32_results).iter().all(|values| values == &[Some(2.0)]));
 
         let (f64_results, columns) = columns.split_at(i64_and_u64_with_f64.len());
-        parse_many_primitives_columns::<Float64Type>(f64_results);
+        assert!(parse_many_primitives_columns::<Float64Type>(f64_results).iter().all(|values| values == &[Some(2.0)]));
 
         // If this failed it means that we forgot to assert some columns
         assert_eq!(columns.len(), 0, "There should be no more columns left in the results");
     }
 
+    #[test]
+    fn decimals_are_widened_to_fit_all_arguments() {
+        let cases = vec![
+            (vec![DataType::Decimal128(10, 2), DataType::Decimal128(5, 4)], DataType::Decimal128(12, 4)),
+            (vec![DataType::Decimal128(10, 2), DataType::Decimal128(10, 2)], DataType::Decimal128(10, 2)),
+            (vec![DataType::Decimal128(10, 2), DataType::Null], DataType::Decimal128(10, 2)),
+            (vec![DataType::Decimal128(5, -2), DataType::Decimal128(3, 1)], DataType::Decimal128(8, 1)),
+
+            // Integers have enough digits for their largest value
+            (vec![DataType::Int8, DataType::Decimal128(2, 1)], DataType::Decimal128(4, 1)),
+            (vec![DataType::Int32, DataType::Decimal128(5, 2)], DataType::Decimal128(12, 2)),
+            (vec![DataType::Int64, DataType::Decimal128(10, 2)], DataType::Decimal128(21, 2)),
+            (vec![DataType::UInt64, DataType::Decimal128(10, 2)], DataType::Decimal128(22, 2)),
+            (vec![DataType::Int16, DataType::UInt32, DataType::Decimal128(3, 3)], DataType::Decimal128(13, 3)),
+
+            // Decimal256 only when the precision does not fit Decimal128
+            (vec![DataType::Decimal128(38, 0), DataType::Decimal128(38, 0)], DataType::Decimal128(38, 0)),
+            (vec![DataType::Decimal128(38, 0), DataType::Decimal128(1, 1)], DataType::Decimal256(39, 1)),
+            (vec![DataType::Int64, DataType::Decimal128(38, 20)], DataType::Decimal256(39, 20)),
+            (vec![DataType::Decimal256(10, 2), DataType::Decimal128(5, 4)], DataType::Decimal128(12, 4)),
+            (vec![DataType::Decimal256(76, 0), DataType::Int8], DataType::Decimal256(76, 0)),
+        ];
+
+        for (data_types, expected) in cases {
+            assert_eq!(find_coerced_type("greatest", &data_types).unwrap(), expected, "{:?}", data_types);
+
+            let reversed = data_types.iter().rev().cloned().collect::<Vec<_>>();
+            assert_eq!(find_coerced_type("greatest", &reversed).unwrap(), expected, "{:?}", reversed);
+        }
+    }
+
+    #[test]
+    fn dictionaries() {
+        let dictionary = |key_type: DataType, value_type: DataType| DataType::Dictionary(Box::new(key_type), Box::new(value_type));
+
+        let cases = vec![
+            // Only dictionaries, the keys are widened to fit all the keys
+            (vec![dictionary(DataType::Int8, DataType::Utf8), dictionary(DataType::Int64, DataType::Utf8)], dictionary(DataType::Int64, DataType::Utf8)),
+            (vec![dictionary(DataType::UInt32, DataType::Utf8), dictionary(DataType::UInt64, DataType::Utf8)], dictionary(DataType::UInt64, DataType::Utf8)),
+
+            // Small keys are widened to 32 bits, as the values used by the result can come from all the dictionaries
+            (vec![dictionary(DataType::Int8, DataType::Utf8), dictionary(DataType::Int8, DataType::Utf8)], dictionary(DataType::Int32, DataType::Utf8)),
+            (vec![dictionary(DataType::UInt8, DataType::Utf8), dictionary(DataType::UInt16, DataType::Utf8)], dictionary(DataType::UInt32, DataType::Utf8)),
+            (vec![dictionary(DataType::Int8, DataType::Int32), dictionary(DataType::UInt8, DataType::Int64), DataType::Null], dictionary(DataType::Int32, DataType::Int64)),
+
+            // The keys can only be coerced to a decimal which can't be a key
+            (vec![dictionary(DataType::UInt64, DataType::Utf8), dictionary(DataType::Int64, DataType::Utf8)], DataType::Utf8),
+
+            // Plain arguments unpack the dictionaries, as their values may not fit the keys
+            (vec![dictionary(DataType::Int8, DataType::Utf8), DataType::Utf8], DataType::Utf8),
+            (vec![dictionary(DataType::Int32, DataType::Int32), DataType::Int64], DataType::Int64),
+        ];
+
+        for (data_types, expected) in cases {
+            assert_eq!(find_coerced_type("greatest", &data_types).unwrap(), expected, "{:?}", data_types);
+
+            let reversed = data_types.iter().rev().cloned().collect::<Vec<_>>();
+            assert_eq!(find_coerced_type("greatest", &reversed).unwrap(), expected, "{:?}", reversed);
+        }
+    }
+
+    #[test]
+    fn decimals_that_do_not_fit_decimal256() {
+        let cases = vec![
+            vec![DataType::Decimal256(76, 0), DataType::Decimal256(76, 1)],
+            vec![DataType::Decimal256(76, 0), DataType::Decimal128(1, 1)],
+            vec![DataType::Decimal256(70, 70), DataType::Int64],
+        ];
+
+        for data_types in cases {
+            let error = find_coerced_type("greatest", &data_types).unwrap_err();
+
+            assert!(error.message().contains("requires a precision of"), "Error message: {}", error.message());
+        }
+    }
+
+    #[tokio::test]
+    async fn widened_decimals_keep_the_values() {
+        let (ctx, greatest) = create_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let max_decimal128 = 10i128.pow(38) - 1;
+
+        let df = df.select(vec![
+            // 123.45 and 1.2345
+            greatest.call(vec![lit(ScalarValue::Decimal128(Some(12345), 10, 2)), lit(ScalarValue::Decimal128(Some(12345), 5, 4))]),
+            // The largest Decimal(38, 0) and 0.5
+            greatest.call(vec![lit(ScalarValue::Decimal128(Some(max_decimal128), 38, 0)), lit(ScalarValue::Decimal128(Some(5), 1, 1))]),
+            // i64::MAX and 0.25
+            greatest.call(vec![lit(i64::MAX), lit(ScalarValue::Decimal128(Some(25), 2, 2))]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(results.column(0).data_type(), &DataType::Decimal128(12, 4));
+        assert_eq!(results.column(0).as_primitive::<Decimal128Type>().value(0), 1234500);
+
+        assert_eq!(results.column(1).data_type(), &DataType::Decimal256(39, 1));
+        assert_eq!(results.column(1).as_primitive::<Decimal256Type>().value(0), i256::from_i128(max_decimal128) * i256::from_i128(10));
+
+        assert_eq!(results.column(2).data_type(), &DataType::Decimal128(21, 2));
+        assert_eq!(results.column(2).as_primitive::<Decimal128Type>().value(0), i64::MAX as i128 * 100);
+    }
+
+    #[test]
+    fn timestamps_use_the_finest_unit_and_a_common_time_zone() {
+        let timestamp = |unit: TimeUnit, time_zone: Option<&str>| DataType::Timestamp(unit, time_zone.map(Into::into));
+
+        let cases = vec![
+            (vec![timestamp(TimeUnit::Second, None), timestamp(TimeUnit::Millisecond, None)], timestamp(TimeUnit::Millisecond, None)),
+            (vec![timestamp(TimeUnit::Nanosecond, None), timestamp(TimeUnit::Microsecond, None)], timestamp(TimeUnit::Nanosecond, None)),
+
+            // The same time zone is kept
+            (vec![timestamp(TimeUnit::Millisecond, Some("Europe/Berlin")), timestamp(TimeUnit::Second, Some("Europe/Berlin"))], timestamp(TimeUnit::Millisecond, Some("Europe/Berlin"))),
+
+            // Different time zones are compared in UTC
+            (vec![timestamp(TimeUnit::Millisecond, Some("UTC")), timestamp(TimeUnit::Nanosecond, Some("Europe/Berlin"))], timestamp(TimeUnit::Nanosecond, Some("UTC"))),
+            (vec![timestamp(TimeUnit::Second, Some("+02:00")), timestamp(TimeUnit::Second, None)], timestamp(TimeUnit::Second, Some("UTC"))),
+
+            // Dates are timestamps at midnight
+            (vec![DataType::Date32, timestamp(TimeUnit::Second, None)], timestamp(TimeUnit::Second, None)),
+            (vec![DataType::Date64, timestamp(TimeUnit::Microsecond, None), DataType::Null], timestamp(TimeUnit::Microsecond, None)),
+            (vec![DataType::Date32, DataType::Date64], DataType::Date64),
+            (vec![DataType::Date32, DataType::Date32], DataType::Date32),
+        ];
+
+        for (data_types, expected) in cases {
+            assert_eq!(find_coerced_type("greatest", &data_types).unwrap(), expected, "{:?}", data_types);
+
+            let reversed = data_types.iter().rev().cloned().collect::<Vec<_>>();
+            assert_eq!(find_coerced_type("greatest", &reversed).unwrap(), expected, "{:?}", reversed);
+        }
+
+        // Dates can't be cast to timestamps with a time zone
+        let error = find_coerced_type("greatest", &[DataType::Date32, timestamp(TimeUnit::Second, Some("UTC"))]).unwrap_err();
+        assert!(error.message().contains("dates can only be compared to timestamps without a time zone"), "Error message: {}", error.message());
+    }
+
+    #[tokio::test]
+    async fn timestamps_compare_the_instant() {
+        let (ctx, greatest) = create_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        // 2024-01-01T10:00:00 UTC
+        let utc_ten = lit(ScalarValue::TimestampMillisecond(Some(1_704_103_200_000), Some("UTC".into())));
+        // 2024-01-01T11:30:00 in Berlin, which is 10:30:00 UTC
+        let berlin_half_past_eleven = lit(ScalarValue::TimestampNanosecond(Some(1_704_105_000_000_000_000), Some("Europe/Berlin".into())));
+        // 2024-01-01T10:45:00 in New York, which is 15:45:00 UTC
+        let new_york_quarter_to_eleven = lit(ScalarValue::TimestampSecond(Some(1_704_123_900), Some("America/New_York".into())));
+        // 2024-01-01T12:00:00 without a time zone, which is read as UTC
+        let no_time_zone_noon = lit(ScalarValue::TimestampMicrosecond(Some(1_704_110_400_000_000), None));
+
+        let df = df.select(vec![
+            greatest.call(vec![utc_ten.clone(), berlin_half_past_eleven.clone()]),
+            greatest.call(vec![new_york_quarter_to_eleven.clone(), berlin_half_past_eleven.clone()]),
+            greatest.call(vec![utc_ten, berlin_half_past_eleven, no_time_zone_noon]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        for column in results.columns() {
+            assert_eq!(column.data_type(), &DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())));
+        }
+
+        let instants = results
+            .columns()
+            .iter()
+            .map(|column| column.as_primitive::<TimestampNanosecondType>().value(0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(instants, vec![1_704_105_000_000_000_000, 1_704_123_900_000_000_000, 1_704_110_400_000_000_000]);
+    }
+
+    #[test]
+    fn numeric_types_are_widened_to_a_common_supertype() {
+        let cases = vec![
+            (vec![DataType::Int8, DataType::Int32], DataType::Int32),
+            (vec![DataType::UInt8, DataType::UInt64], DataType::UInt64),
+
+            // Signed with unsigned need a wider signed integer, which might not be one of the arguments
+            (vec![DataType::Int8, DataType::UInt8], DataType::Int16),
+            (vec![DataType::Int16, DataType::UInt16], DataType::Int32),
+            (vec![DataType::Int32, DataType::UInt32], DataType::Int64),
+            (vec![DataType::Int8, DataType::UInt32], DataType::Int64),
+            (vec![DataType::Int64, DataType::UInt8], DataType::Int64),
+            (vec![DataType::Int64, DataType::UInt64], DataType::Decimal128(20, 0)),
+            (vec![DataType::Int8, DataType::UInt64], DataType::Decimal128(20, 0)),
+            (vec![DataType::Int8, DataType::UInt8, DataType::Null], DataType::Int16),
+
+            // Floating points win over integers and decimals
+            (vec![DataType::Int64, DataType::Float32], DataType::Float32),
+            (vec![DataType::UInt64, DataType::Float16], DataType::Float32),
+            (vec![DataType::Float16, DataType::Float16], DataType::Float16),
+            (vec![DataType::Float32, DataType::Float64], DataType::Float64),
+            (vec![DataType::Decimal128(10, 2), DataType::Float32], DataType::Float64),
+
+            // Decimals with unsigned integers
+            (vec![DataType::UInt64, DataType::Int8, DataType::Decimal128(5, 2)], DataType::Decimal128(22, 2)),
+        ];
+
+        for (data_types, expected) in cases {
+            assert_eq!(find_coerced_type("greatest", &data_types).unwrap(), expected, "{:?}", data_types);
+
+            let reversed = data_types.iter().rev().cloned().collect::<Vec<_>>();
+            assert_eq!(find_coerced_type("greatest", &reversed).unwrap(), expected, "{:?}", reversed);
+        }
+    }
+
+    #[tokio::test]
+    async fn widened_integers_keep_the_values() {
+        let (ctx, greatest) = create_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(vec![
+            greatest.call(vec![lit(-1i8), lit(200u8)]),
+            greatest.call(vec![lit(i64::MIN), lit(u64::MAX)]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(results.column(0).data_type(), &DataType::Int16);
+        assert_eq!(results.column(0).as_primitive::<Int16Type>().value(0), 200);
+
+        assert_eq!(results.column(1).data_type(), &DataType::Decimal128(20, 0));
+        assert_eq!(results.column(1).as_primitive::<Decimal128Type>().value(0), u64::MAX as i128);
+    }
 }
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod dictionary_tests {
+    use crate::tests::utils::{create_context, get_combined_results, parse_string_column};
+    use crate::GreatestUdf;
+    use datafusion::arrow::array::{ArrayRef, DictionaryArray, RecordBatch, StringArray};
+    use datafusion::arrow::compute::cast;
+    use datafusion::arrow::datatypes::{DataType, Int32Type, Int8Type};
+    use datafusion_expr::{col, lit, ColumnarValue, ScalarUDFImpl};
+    use std::sync::Arc;
+
+    fn create_batch() -> RecordBatch {
+        let a: ArrayRef = Arc::new(vec![Some("b"), None, Some("a"), Some("d")].into_iter().collect::<DictionaryArray<Int32Type>>());
+        let b: ArrayRef = Arc::new(vec![Some("a"), Some("c"), None, Some("e")].into_iter().collect::<DictionaryArray<Int32Type>>());
+        let small_keys: ArrayRef = Arc::new(vec![Some("c"), Some("a"), None, Some("a")].into_iter().collect::<DictionaryArray<Int8Type>>());
+        let plain: ArrayRef = Arc::new(StringArray::from(vec![Some("a"), Some("d"), None, Some("f")]));
+
+        RecordBatch::try_from_iter(vec![("a", a), ("b", b), ("small_keys", small_keys), ("plain", plain)]).unwrap()
+    }
+
+    fn dictionary_of(key_type: DataType) -> DataType {
+        DataType::Dictionary(Box::new(key_type), Box::new(DataType::Utf8))
+    }
+
+    #[tokio::test]
+    async fn dictionaries_stay_encoded() {
+        let (ctx, greatest) = create_context();
+
+        ctx.register_batch("t", create_batch()).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(results.column(0).data_type(), &dictionary_of(DataType::Int32));
+        assert_eq!(
+            parse_string_column(&cast(results.column(0), &DataType::Utf8).unwrap()),
+            vec![Some("b".to_string()), Some("c".to_string()), Some("a".to_string()), Some("e".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn dictionaries_with_different_keys_use_the_widest_key() {
+        let (ctx, greatest) = create_context();
+
+        ctx.register_batch("t", create_batch()).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![greatest.call(vec![col("small_keys"), col("a")])]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(results.column(0).data_type(), &dictionary_of(DataType::Int32));
+        assert_eq!(
+            parse_string_column(&cast(results.column(0), &DataType::Utf8).unwrap()),
+            vec![Some("c".to_string()), Some("a".to_string()), Some("a".to_string()), Some("d".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn plain_values_unpack_the_dictionaries() {
+        let (ctx, greatest) = create_context();
+
+        ctx.register_batch("t", create_batch()).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![
+            greatest.call(vec![col("small_keys"), col("plain")]),
+            greatest.call(vec![col("a"), col("b"), lit("c")]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(results.column(0).data_type(), &DataType::Utf8);
+        assert_eq!(
+            parse_string_column(results.column(0)),
+            vec![Some("c".to_string()), Some("d".to_string()), None, Some("f".to_string())]
+        );
+
+        assert_eq!(results.column(1).data_type(), &DataType::Utf8);
+        assert_eq!(
+            parse_string_column(results.column(1)),
+            vec![Some("c".to_string()), Some("c".to_string()), Some("c".to_string()), Some("e".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn plain_column_with_more_values_than_the_dictionary_keys() {
+        let (ctx, _) = create_context();
+
+        let values = (0..300).map(|i| format!("value {i:03}")).collect::<Vec<_>>();
+
+        let small: ArrayRef = Arc::new((0..300).map(|i| Some(["a", "z"][i % 2])).collect::<DictionaryArray<Int8Type>>());
+        let plain: ArrayRef = Arc::new(StringArray::from_iter_values(&values));
+
+        ctx.register_batch("t", RecordBatch::try_from_iter(vec![("small", small), ("plain", plain)]).unwrap()).unwrap();
+
+        let df = ctx.sql("SELECT greatest(small, plain) FROM t").await.unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        let expected = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Some(if i % 2 == 0 { value.clone() } else { "z".to_string() }))
+            .collect::<Vec<_>>();
+
+        assert_eq!(parse_string_column(results.column(0)), expected);
+    }
+
+    #[tokio::test]
+    async fn dictionaries_with_more_used_values_than_the_keys() {
+        let (ctx, _) = create_context();
+
+        // Each dictionary has less than 128 values, but together the largest values are 200 different values
+        let a = (0..200).map(|i| if i < 100 { format!("z{}", i) } else { "a".to_string() }).collect::<Vec<_>>();
+        let b = (0..200).map(|i| if i < 100 { "b".to_string() } else { format!("y{}", i) }).collect::<Vec<_>>();
+
+        let a_array: ArrayRef = Arc::new(a.iter().map(|value| value.as_str()).collect::<DictionaryArray<Int8Type>>());
+        let b_array: ArrayRef = Arc::new(b.iter().map(|value| value.as_str()).collect::<DictionaryArray<Int8Type>>());
+
+        ctx.register_batch("t", RecordBatch::try_from_iter(vec![("a", a_array.clone()), ("b", b_array.clone())]).unwrap()).unwrap();
+
+        let df = ctx.sql("SELECT greatest(a, b) FROM t").await.unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        let expected = a.into_iter().zip(b).map(|(a, b)| Some(a.max(b))).collect::<Vec<_>>();
+
+        // The keys are widened so all the used values fit
+        assert_eq!(results.column(0).data_type(), &dictionary_of(DataType::Int32));
+        assert_eq!(parse_string_column(&cast(results.column(0), &DataType::Utf8).unwrap()), expected);
+
+        // Invoking directly with the small keys unpacks the result, as the used values don't fit the keys
+        let result = GreatestUdf::new().invoke(&[ColumnarValue::Array(a_array), ColumnarValue::Array(b_array)]).unwrap().into_array(200).unwrap();
+
+        assert_eq!(result.data_type(), &DataType::Utf8);
+        assert_eq!(parse_string_column(&result), expected);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod greatest_by_tests {
+    use crate::tests::utils::{create_context_with_udf, create_primitive_array, generate_optional_values, get_combined_results, get_string_result_as_matrix, parse_primitive_column, parse_string_column};
+    use crate::{GreatestByUdf, GreatestIndexUdf, NullHandling};
+    use datafusion::arrow::array::{ArrayRef, RecordBatch, StringArray};
+    use datafusion::arrow::datatypes::{Float64Type, Int32Type, Int64Type, UInt64Type};
+    use datafusion::prelude::SessionContext;
+    use datafusion_expr::{col, lit, ScalarUDF};
+    use std::sync::Arc;
+
+    fn create_vendors_context(udf: GreatestByUdf) -> (SessionContext, ScalarUDF) {
+        let (ctx, greatest_by) = create_context_with_udf(ScalarUDF::from(udf));
+
+        let batch = RecordBatch::try_from_iter(vec![
+            ("rating_a", create_primitive_array::<Int32Type>(vec![Some(4), Some(2), None, Some(3), None]) as ArrayRef),
+            ("rating_b", create_primitive_array::<Int32Type>(vec![Some(5), Some(1), Some(1), Some(3), None])),
+            ("vendor_a", Arc::new(StringArray::from(vec![Some("a1"), Some("a2"), Some("a3"), Some("a4"), Some("a5")]))),
+            ("vendor_b", Arc::new(StringArray::from(vec![Some("b1"), None, Some("b3"), Some("b4"), Some("b5")]))),
+        ]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        (ctx, greatest_by)
+    }
+
+    #[tokio::test]
+    async fn value_of_the_greatest_key() {
+        let (ctx, greatest_by) = create_vendors_context(GreatestByUdf::new());
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![greatest_by.call(vec![col("rating_a"), col("rating_b"), col("vendor_a"), col("vendor_b")])]).unwrap();
+
+        let results = get_string_result_as_matrix(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![
+            Some("b1".to_string()),
+            Some("a2".to_string()),
+            Some("b3".to_string()),
+            // Ties are resolved to the key with the lowest position, the same as greatest_index
+            Some("a4".to_string()),
+            None,
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn null_keys_are_propagated() {
+        let (ctx, greatest_by) = create_vendors_context(GreatestByUdf::new().with_null_handling(NullHandling::Propagate));
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![greatest_by.call(vec![col("rating_a"), col("rating_b"), col("vendor_a"), col("vendor_b")])]).unwrap();
+
+        let results = get_string_result_as_matrix(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some("b1".to_string()), Some("a2".to_string()), None, Some("a4".to_string()), None]]);
+    }
+
+    #[tokio::test]
+    async fn value_at_the_position_of_greatest_index() {
+        let (ctx, greatest_by) = create_context_with_udf(ScalarUDF::from(GreatestByUdf::new()));
+        let greatest_index = ScalarUDF::from(GreatestIndexUdf::new());
+
+        // Small range of keys so there are a lot of ties
+        let keys = (0..3)
+            .map(|_| generate_optional_values::<i8>(200, Some(0.3)).into_iter().map(|v| v.map(|v| (v % 4) as i32)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let values = (0..3)
+            .map(|_| generate_optional_values::<f64>(200, Some(0.1)))
+            .collect::<Vec<_>>();
+
+        let batch = RecordBatch::try_from_iter(
+            keys.iter().enumerate().map(|(i, keys)| (format!("k{}", i), create_primitive_array::<Int32Type>(keys.clone()) as ArrayRef))
+                .chain(values.iter().enumerate().map(|(i, values)| (format!("v{}", i), create_primitive_array::<Float64Type>(values.clone()) as ArrayRef)))
+        ).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let key_args = vec![col("k0"), col("k1"), col("k2")];
+        let value_args = vec![col("v0"), col("v1"), col("v2")];
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![
+            greatest_by.call([key_args.clone(), value_args].concat()),
+            greatest_index.call(key_args),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        let greatest_values = parse_primitive_column::<Float64Type>(results.column(0));
+        let positions = parse_primitive_column::<UInt64Type>(results.column(1));
+
+        for (row, (value, position)) in greatest_values.iter().zip(positions).enumerate() {
+            match position {
+                None => assert_eq!(value, &None, "value is null when the greatest key is null, row {}", row),
+                Some(position) => assert_eq!(&values[position as usize - 1][row], value, "row {}", row),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn scalar_keys_and_values() {
+        let (ctx, greatest_by) = create_vendors_context(GreatestByUdf::new());
+
+        let df = ctx.table("t").await.unwrap();
+        let df = df.select(vec![
+            // Scalar key with array keys
+            greatest_by.call(vec![col("rating_a"), lit(3), col("vendor_a"), lit("default")]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(parse_string_column(results.column(0)), vec![
+            Some("a1".to_string()),
+            Some("default".to_string()),
+            Some("default".to_string()),
+            // Ties are resolved to the key with the lowest position, the same as greatest_index
+            Some("a4".to_string()),
+            Some("default".to_string()),
+        ]);
+
+        let df = ctx.sql("SELECT greatest_by(1, 3, 'a', 'b'), greatest_by(NULL, NULL, 'a', 'b')").await.unwrap();
+
+        let results = get_string_result_as_matrix(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some("b".to_string())], vec![None]]);
+    }
+
+    #[tokio::test]
+    async fn keys_and_values_are_coerced_separately() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(GreatestByUdf::new()));
+
+        let df = ctx.sql("SELECT greatest_by(CAST(1 AS TINYINT), 2.5, CAST(10 AS INT), CAST(20 AS BIGINT))").await.unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        assert_eq!(parse_primitive_column::<Int64Type>(results.column(0)), vec![Some(20)]);
+    }
+
+    #[tokio::test]
+    async fn invalid_number_of_arguments() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(GreatestByUdf::new()));
+
+        for query in ["SELECT greatest_by(1, 'a')", "SELECT greatest_by(1, 2, 'a')", "SELECT greatest_by(1, 2, 3, 'a', 'b')"] {
+            let error = ctx.sql(query).await.expect_err("greatest_by should fail with invalid number of arguments");
+
+            assert!(error.to_string().contains("It requires at least 2 keys followed by the same number of values."), "{}: {}", query, error);
+        }
+    }
+}
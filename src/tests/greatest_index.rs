@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod greatest_index_tests {
+    use crate::tests::utils::{create_context, create_context_with_udf, create_empty_data_frame, create_primitive_array, generate_optional_values, get_combined_results, get_primitive_result_as_matrix, parse_primitive_column};
+    use crate::{vec_with_lit, GreatestIndexUdf};
+    use datafusion::arrow::array::{ArrayRef, DictionaryArray, RecordBatch};
+    use datafusion::arrow::datatypes::{Int32Type, Int8Type, UInt64Type};
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{col, lit, ColumnarValue, ScalarUDF, ScalarUDFImpl};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn position_holds_the_greatest_value() {
+        let (ctx, greatest) = create_context();
+        let greatest_index = ScalarUDF::from(GreatestIndexUdf::new());
+
+        // Small range of values so there are a lot of ties
+        let columns = (0..3)
+            .map(|_| generate_optional_values::<i8>(100, Some(0.3)).into_iter().map(|v| v.map(|v| (v % 4) as i32)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let batch = RecordBatch::try_from_iter(
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, values)| (format!("c{}", i), create_primitive_array::<Int32Type>(values.clone()) as ArrayRef))
+        ).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let args = vec![col("c0"), col("c1"), col("c2")];
+        let df = df.select(vec![greatest.call(args.clone()), greatest_index.call(args)]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        let greatest_values = parse_primitive_column::<Int32Type>(results.column(0));
+        let positions = parse_primitive_column::<UInt64Type>(results.column(1));
+
+        for (row, (value, position)) in greatest_values.iter().zip(positions).enumerate() {
+            match position {
+                None => assert_eq!(value, &None, "position is null only when the greatest value is null, row {}", row),
+                Some(position) => assert_eq!(&columns[position as usize - 1][row], value, "row {}", row),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn ties_are_resolved_to_the_lowest_position() {
+        let (ctx, greatest_index) = create_context_with_udf(ScalarUDF::from(GreatestIndexUdf::new()));
+
+        let a: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(1), Some(5), None, Some(2)]);
+        let b: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(1), Some(3), None, None]);
+        let c: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(0), Some(5), None, None]);
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b), ("c", c)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![
+            greatest_index.call(vec![col("a"), col("b"), col("c")]),
+            greatest_index.call(vec![col("a"), col("a")]),
+            // Scalars and arrays resolve ties the same way
+            greatest_index.call(vec![lit(5), col("a"), lit(5)]),
+            greatest_index.call(vec![col("b"), lit(1), col("a")]),
+            greatest_index.call(vec![lit(1), lit(1)]),
+        ]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<UInt64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(1), Some(1), None, Some(1)],
+            vec![Some(1), Some(1), None, Some(1)],
+            vec![Some(1), Some(1), Some(1), Some(1)],
+            vec![Some(1), Some(3), Some(2), Some(3)],
+            vec![Some(1), Some(1), Some(1), Some(1)],
+        ]);
+    }
+
+    #[tokio::test]
+    async fn scalars_and_arrays() {
+        let (ctx, greatest_index) = create_context_with_udf(ScalarUDF::from(GreatestIndexUdf::new()));
+
+        let a: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(1), Some(7), None]);
+        let b: ArrayRef = create_primitive_array::<Int32Type>(vec![Some(2), Some(3), None]);
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![
+            greatest_index.call(vec![col("a"), lit(5), col("b")]),
+            greatest_index.call(vec![col("a"), lit(ScalarValue::Null), col("b")]),
+        ]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<UInt64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(2), Some(1), Some(2)],
+            vec![Some(3), Some(1), None],
+        ]);
+    }
+
+    #[tokio::test]
+    async fn scalars() {
+        let (ctx, greatest_index) = create_context_with_udf(ScalarUDF::from(GreatestIndexUdf::new()));
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(vec![
+            greatest_index.call(vec_with_lit![1, 3, 2]),
+            greatest_index.call(vec_with_lit![ScalarValue::Null, 3, 3]),
+            greatest_index.call(vec_with_lit![ScalarValue::Null, ScalarValue::Int32(None)]),
+        ]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<UInt64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(2)],
+            vec![Some(2)],
+            vec![None],
+        ]);
+    }
+
+    #[test]
+    fn dictionaries_with_more_used_values_than_the_keys() {
+        // Each dictionary has less than 128 values, but together the largest values are 200 different values
+        let a = (0..200).map(|i| if i < 100 { format!("z{}", i) } else { "a".to_string() }).collect::<Vec<_>>();
+        let b = (0..200).map(|i| if i < 100 { "b".to_string() } else { format!("y{}", i) }).collect::<Vec<_>>();
+
+        let args = [&a, &b]
+            .map(|values| ColumnarValue::Array(Arc::new(values.iter().map(|value| value.as_str()).collect::<DictionaryArray<Int8Type>>())));
+
+        let positions = GreatestIndexUdf::new().invoke(&args).unwrap().into_array(200).unwrap();
+
+        let expected = (0..200).map(|i| Some(if i < 100 { 1 } else { 2 })).collect::<Vec<_>>();
+
+        assert_eq!(parse_primitive_column::<UInt64Type>(&positions), expected);
+    }
+}
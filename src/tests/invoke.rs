@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod invoke_tests {
+    use crate::{GreatestIndexUdf, GreatestUdf, LeastUdf};
+    use datafusion::arrow::array::{ArrayRef, Int32Array, StringArray, UInt64Array};
+    use datafusion::error::DataFusionError;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{ColumnarValue, ScalarUDFImpl};
+    use std::sync::Arc;
+
+    fn udfs() -> Vec<Box<dyn ScalarUDFImpl>> {
+        vec![
+            Box::new(GreatestUdf::new()),
+            Box::new(LeastUdf::new()),
+            Box::new(GreatestIndexUdf::new()),
+        ]
+    }
+
+    fn int_array() -> ColumnarValue {
+        ColumnarValue::Array(Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef)
+    }
+
+    #[test]
+    fn invoke_with_less_than_2_arguments() {
+        for udf in udfs() {
+            for args in [vec![], vec![int_array()], vec![ColumnarValue::Scalar(ScalarValue::Int32(Some(1)))]] {
+                let error = udf.invoke(&args).expect_err("should fail with less than 2 arguments");
+
+                assert!(matches!(error, DataFusionError::Execution(_)), "{} returned {:?}", udf.name(), error);
+                assert!(error.message().contains(&format!("Expected at least 2 arguments, got {}", args.len())), "{} returned {:?}", udf.name(), error);
+            }
+        }
+    }
+
+    #[test]
+    fn invoke_with_different_types() {
+        let string_array = ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("a"), Some("b"), None])) as ArrayRef);
+        let string_scalar = ColumnarValue::Scalar(ScalarValue::Utf8(Some("a".to_string())));
+        let int_scalar = ColumnarValue::Scalar(ScalarValue::Int32(Some(1)));
+
+        let cases = vec![
+            vec![int_array(), string_array.clone()],
+            vec![string_array.clone(), int_array()],
+            vec![int_array(), string_scalar.clone()],
+            vec![string_array, int_scalar.clone()],
+            vec![int_scalar, string_scalar],
+        ];
+
+        for udf in udfs() {
+            for args in &cases {
+                assert!(udf.invoke(args).is_err(), "{} should fail for {:?}", udf.name(), args);
+            }
+        }
+    }
+
+    #[test]
+    fn invoke_with_valid_arguments() {
+        let args = vec![int_array(), ColumnarValue::Scalar(ScalarValue::Int32(Some(2)))];
+
+        let expected: Vec<ArrayRef> = vec![
+            // greatest, the null is skipped
+            Arc::new(Int32Array::from(vec![2, 2, 3])),
+            // least, the null is skipped
+            Arc::new(Int32Array::from(vec![1, 2, 2])),
+            // greatest_index, 1-based position of the greatest value
+            Arc::new(UInt64Array::from(vec![2, 2, 1])),
+        ];
+
+        for (udf, expected) in udfs().into_iter().zip(expected) {
+            let result = udf.invoke(&args).unwrap().into_array(3).unwrap();
+
+            assert_eq!(&result, &expected, "{}", udf.name());
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod single_type;
+mod scalars;
+mod scalars_and_arrays;
@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod least_scalars_tests {
+    //! These tests check that calling least only on scalars values
+    //!
+    //! This does not include columns
+
+    use std::sync::Arc;
+    use datafusion::arrow::array::ListArray;
+    use crate::helpers::{Permutation, Transpose};
+    use crate::tests::utils::{create_least_context, create_empty_data_frame, get_combined_results, get_list_result_as_matrix, get_primitive_result_as_matrix, parse_primitive_column, parse_string_column};
+    use crate::vec_with_lit;
+    use datafusion::arrow::datatypes::{DataType, Float32Type, Float64Type, Int32Type, Int64Type, Int8Type};
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{lit, Expr};
+
+    #[tokio::test]
+    async fn i8_without_nulls() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(vec![least.call(vec_with_lit![2i8, 10i8])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(2)]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn all_null() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(vec![least.call(vec_with_lit![ScalarValue::Null, ScalarValue::Null, ScalarValue::Null])]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+
+        let results_data_type = results.columns().iter().map(|col| col.data_type()).cloned().collect::<Vec<_>>();
+
+        assert_eq!(results_data_type, vec![DataType::Null]);
+    }
+
+    #[tokio::test]
+    async fn i8_tests() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        // Only integers - 1, 2, 3 => 1
+        let only_integers_permutations = vec_with_lit![1i8, 2i8, 3i8].permutation(3);
+
+        // 1 None, 2 integers - 1, None, 5 => 1
+        let two_integers_one_none_permutations = vec_with_lit![1i8, ScalarValue::Null, 5i8].permutation(3);
+
+        // 2 None, 1 integer - None, None, 1 => 1
+        let two_none_one_integer_permutations = vec_with_lit![ScalarValue::Null, ScalarValue::Null, 1i8].permutation(3);
+
+        let df = df.select([
+            only_integers_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+            two_integers_one_none_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+            two_none_one_integer_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+        ].concat()).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap().transpose();
+
+        assert_eq!(results, vec![[
+            vec![Some(1); only_integers_permutations.len()],
+            vec![Some(1); two_integers_one_none_permutations.len()],
+            vec![Some(1); two_none_one_integer_permutations.len()],
+        ].concat()]);
+    }
+
+    #[tokio::test]
+    async fn f32_tests() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        // Only f32 - 1, 2, 3 => 1
+        let only_f32_permutations = vec_with_lit![1f32, 2f32, 3f32].permutation(3);
+
+        // 1 None, 2 f32 - 1, None, 5 => 1
+        let two_f32_one_none_permutations = vec_with_lit![1f32, ScalarValue::Null, 5f32].permutation(3);
+
+        // 2 None, 1 f32 - None, None, 1 => 1
+        let two_none_one_f32_permutations = vec_with_lit![ScalarValue::Null, ScalarValue::Null, 1f32].permutation(3);
+
+        let df = df.select([
+            only_f32_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+            two_f32_one_none_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+            two_none_one_f32_permutations.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+        ].concat()).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Float32Type>(df).await.unwrap().transpose();
+
+        assert_eq!(results, vec![[
+            vec![Some(1f32); only_f32_permutations.len()],
+            vec![Some(1f32); two_f32_one_none_permutations.len()],
+            vec![Some(1f32); two_none_one_f32_permutations.len()],
+        ].concat()]);
+    }
+
+    #[tokio::test]
+    async fn float_nan_tests() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+
+        let values = vec_with_lit![
+            1f32,
+            ScalarValue::Null,
+            f32::NAN,
+            f32::INFINITY
+        ].permutation(4);
+
+        let df = df.select([
+            values.iter().map(|v| least.call(v.clone())).collect::<Vec<_>>(),
+        ].concat()).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Float32Type>(df).await.unwrap().transpose();
+
+        // NaN is greater than any value, even infinity, so it is never the least
+        for result in results {
+            for r in result {
+                assert_eq!(r, Some(1f32));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_tests() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let expressions: Vec<Expr> = [
+            vec![
+                // Least is 1 as we look at the first item in each list first
+                Some(vec![Some(2), Some(100)]),
+                Some(vec![Some(1), Some(200)]),
+            ],
+            vec![
+                // Least is None as None is smaller than 1
+                Some(vec![None, Some(100)]),
+                Some(vec![Some(1), Some(200)]),
+            ],
+            vec![
+                // Least is 100 as if the first item is equal we look at the second item
+                Some(vec![Some(6), Some(100)]),
+                Some(vec![Some(6), Some(200)]),
+            ],
+            vec![
+                // Least is 100 as if the first item is equal we look at the second item
+                Some(vec![None, Some(100)]),
+                Some(vec![None, Some(200)]),
+            ],
+            vec![
+                // Least is the empty list as no value is smaller than having a value
+                Some(vec![None]),
+                Some(vec![]),
+            ],
+            vec![
+                // Least is 0 as 0 is smaller than 1, even though the length is different
+                Some(vec![Some(1)]),
+                Some(vec![Some(0), Some(4)]),
+            ],
+            vec![
+                // Least is the shorter list as nothing is smaller than 4
+                Some(vec![Some(0)]),
+                Some(vec![Some(0), Some(4)]),
+            ],
+            vec![
+                // Least is None as None is smaller than 0
+                Some(vec![None, Some(3)]),
+                Some(vec![Some(0)]),
+            ],
+        ]
+            .iter()
+            .map(|args| {
+                args
+                    .iter()
+                    .map(|list| lit(ScalarValue::List(Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(Some(list.clone()))))))
+                    .collect()
+            })
+            .map(|args| least.call(args))
+            .collect();
+
+        let df = df.select(expressions).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap().transpose();
+
+        assert_eq!(results, vec![vec![
+            Some(vec![Some(1), Some(200)]),
+            Some(vec![None, Some(100)]),
+            Some(vec![Some(6), Some(100)]),
+            Some(vec![None, Some(100)]),
+            Some(vec![]),
+            Some(vec![Some(0), Some(4)]),
+            Some(vec![Some(0)]),
+            Some(vec![None, Some(3)]),
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn multiple_types() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(vec![
+
+            // i8, i16, i32, i64 all in the bounds of i8
+            least.call(vec_with_lit![4i8, 2i16, 3i32, -2i64]),
+
+            // i8, i16, i32, i64 all in the bounds of i16
+            least.call(vec_with_lit![i8::MIN, -134i16, i16::MAX as i32, i16::MIN as i64]),
+
+            // i8, i16, i32, i64 all in the bounds of i32
+            least.call(vec_with_lit![i8::MIN, i16::MIN, i32::MAX, i32::MIN as i64]),
+
+            // i8, i16, i32, i64 all in the bounds of i64
+            least.call(vec_with_lit![i8::MIN, i16::MIN, i32::MIN, i64::MIN]),
+
+            // f32, i8, i16, i32, i64 all in the bounds of f32
+            least.call(vec_with_lit![f32::MIN, i8::MIN, i16::MIN, i32::MIN, i64::MIN]),
+
+            // f32, f64, i8, i16, i32, i64 all in the bounds of f32
+            least.call(vec_with_lit![-43.342f32, f32::MIN as f64, i8::MIN, i16::MIN, i32::MIN, i64::MIN]),
+
+            // f32, f64, i8, i16, i32, i64 all in the bounds of f64
+            least.call(vec_with_lit![f32::MIN, f64::MIN, i8::MIN, i16::MIN, i32::MIN, i64::MIN]),
+
+            // string
+            least.call(vec_with_lit!["hey", "you"]),
+        ]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+        let mut columns: Vec<_> = results.columns().iter().collect();
+
+        assert_eq!(parse_primitive_column::<Int64Type>(columns.remove(0)), vec![Some(-2i64)]);
+        assert_eq!(parse_primitive_column::<Int64Type>(columns.remove(0)), vec![Some(i16::MIN as i64)]);
+        assert_eq!(parse_primitive_column::<Int64Type>(columns.remove(0)), vec![Some(i32::MIN as i64)]);
+        assert_eq!(parse_primitive_column::<Int64Type>(columns.remove(0)), vec![Some(i64::MIN)]);
+        assert_eq!(parse_primitive_column::<Float32Type>(columns.remove(0)), vec![Some(f32::MIN)]);
+        assert_eq!(parse_primitive_column::<Float64Type>(columns.remove(0)), vec![Some(f32::MIN as f64)]);
+        assert_eq!(parse_primitive_column::<Float64Type>(columns.remove(0)), vec![Some(f64::MIN)]);
+        assert_eq!(parse_string_column(columns.remove(0)), vec![Some("hey".to_string())]);
+
+        // If this failed it means that we forgot to assert some columns
+        assert_eq!(columns.len(), 0, "There should be no more columns left in the results");
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod list_greatest_tests {
+    use crate::tests::utils::{create_context_with_udf, generate_list_values, get_primitive_result_as_matrix, get_string_result_as_matrix};
+    use crate::{ListGreatestUdf, NanOrdering, NullHandling};
+    use datafusion::arrow::array::{Array, ArrayRef, AsArray, FixedSizeListArray, LargeListArray, ListArray, RecordBatch, StringArray};
+    use datafusion::arrow::buffer::OffsetBuffer;
+    use datafusion::arrow::datatypes::{DataType, Field, Float64Type, Int32Type, Int64Type};
+    use datafusion::prelude::SessionContext;
+    use datafusion_expr::{col, ScalarUDF};
+    use std::sync::Arc;
+
+    fn create_context_with_list(list: ArrayRef, udf: ListGreatestUdf) -> (SessionContext, ScalarUDF) {
+        let (ctx, list_greatest) = create_context_with_udf(ScalarUDF::from(udf));
+
+        ctx.register_batch("t", RecordBatch::try_from_iter(vec![("list", list)]).unwrap()).unwrap();
+
+        (ctx, list_greatest)
+    }
+
+    fn int_lists() -> ArrayRef {
+        Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(5), Some(3)]),
+            Some(vec![Some(-1), None, Some(-8)]),
+            Some(vec![None, None]),
+            Some(vec![]),
+            None,
+            Some(vec![Some(7)]),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn greatest_item_of_each_list() {
+        let (ctx, list_greatest) = create_context_with_list(int_lists(), ListGreatestUdf::new());
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(5), Some(-1), None, None, None, Some(7)]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn null_items_are_propagated() {
+        let (ctx, list_greatest) = create_context_with_list(int_lists(), ListGreatestUdf::new().with_null_handling(NullHandling::Propagate));
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(5), None, None, None, None, Some(7)]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn random_lists_match_the_greatest_item() {
+        let lists = generate_list_values::<i32>(1000, 0..10, Some(0.1), Some(0.2));
+
+        let (ctx, list_greatest) = create_context_with_list(
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(lists.clone())),
+            ListGreatestUdf::new(),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        let expected = lists
+            .iter()
+            .map(|list| list.as_ref().and_then(|items| items.iter().flatten().max().copied()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(results, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn nan_items() {
+        let list: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+            Some(vec![Some(1.0), Some(f64::NAN), Some(3.0)]),
+            Some(vec![Some(f64::NAN), None]),
+            Some(vec![Some(f64::INFINITY), Some(-1.0)]),
+        ]));
+
+        for (nan_ordering, expected) in [
+            (NanOrdering::Greatest, vec![Some(f64::NAN), Some(f64::NAN), Some(f64::INFINITY)]),
+            (NanOrdering::Skip, vec![Some(3.0), Some(f64::NAN), Some(f64::INFINITY)]),
+            (NanOrdering::Propagate, vec![Some(f64::NAN), Some(f64::NAN), Some(f64::INFINITY)]),
+        ] {
+            let (ctx, list_greatest) = create_context_with_list(list.clone(), ListGreatestUdf::new().with_nan_ordering(nan_ordering));
+
+            let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+            let results = get_primitive_result_as_matrix::<Float64Type>(df).await.unwrap();
+
+            // Compare the bits so NaN is equal to NaN
+            assert_eq!(
+                results[0].iter().map(|v| v.map(f64::to_bits)).collect::<Vec<_>>(),
+                expected.iter().map(|v| v.map(f64::to_bits)).collect::<Vec<_>>(),
+                "{:?}", nan_ordering
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn large_list_of_strings() {
+        let strings = StringArray::from(vec![Some("b"), Some("abc"), None, Some("a"), Some("")]);
+        let list: ArrayRef = Arc::new(LargeListArray::new(
+            Arc::new(Field::new("item", DataType::Utf8, true)),
+            OffsetBuffer::new(vec![0i64, 3, 3, 5].into()),
+            Arc::new(strings),
+            None,
+        ));
+
+        let (ctx, list_greatest) = create_context_with_list(list, ListGreatestUdf::new());
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = get_string_result_as_matrix(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some("b".to_string()), None, Some("a".to_string())]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn fixed_size_list() {
+        let list: ArrayRef = Arc::new(FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(5)]),
+            None,
+            Some(vec![None, Some(-2)]),
+        ], 2));
+
+        // Slice to make sure the offset of the list is used
+        let (ctx, list_greatest) = create_context_with_list(list.slice(1, 2), ListGreatestUdf::new());
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![None, Some(-2)]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn nested_items() {
+        let inner = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(1), Some(3)]),
+            Some(vec![Some(4)]),
+            None,
+            Some(vec![]),
+        ]);
+        let list: ArrayRef = Arc::new(ListArray::new(
+            Arc::new(Field::new("item", inner.data_type().clone(), true)),
+            OffsetBuffer::new(vec![0, 3, 5].into()),
+            Arc::new(inner),
+            None,
+        ));
+
+        let (ctx, list_greatest) = create_context_with_list(list, ListGreatestUdf::new());
+
+        let df = ctx.table("t").await.unwrap().select(vec![list_greatest.call(vec![col("list")])]).unwrap();
+
+        let results = df.collect().await.unwrap();
+        let result = results[0].column(0).as_list::<i32>();
+
+        assert_eq!(result.value(0).as_primitive::<Int32Type>().values().to_vec(), vec![4]);
+        assert_eq!(result.value(1).as_primitive::<Int32Type>().values().to_vec(), Vec::<i32>::new());
+    }
+
+    #[tokio::test]
+    async fn scalar_list_from_sql() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(ListGreatestUdf::new()));
+
+        let df = ctx.sql("SELECT list_greatest(make_array(1, 5, 3)), list_greatest(make_array(NULL, 2))").await.unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(5)], vec![Some(2)]]);
+    }
+
+    #[tokio::test]
+    async fn not_a_list() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(ListGreatestUdf::new()));
+
+        let error = ctx.sql("SELECT list_greatest(1)").await.expect_err("list_greatest should fail on a non list argument");
+
+        assert!(error.to_string().contains("list_greatest expects a list argument, got Int64"), "{}", error);
+
+        let error = ctx.sql("SELECT list_greatest(make_array(1), make_array(2))").await.expect_err("list_greatest should fail with 2 arguments");
+
+        assert!(error.to_string().contains("list_greatest was called with 2 arguments. It requires exactly 1."), "{}", error);
+    }
+}
@@ -4,3 +4,18 @@ mod scalars;
 pub(crate) mod utils;
 mod coerce;
 mod scalars_and_arrays;
+mod least;
+mod register;
+mod null_handling;
+mod nan;
+mod greatest_index;
+mod simplify;
+mod output_ordering;
+mod bounds;
+mod rewrite_greatest_predicate;
+mod dictionary;
+mod invoke;
+mod list_greatest;
+mod zip_greatest;
+mod greatest_by;
+mod nullable;
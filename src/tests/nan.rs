@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod nan_tests {
+    //! NaN, signed zeros and infinities for each `NanOrdering`
+    //!
+    //! Each case is checked both with columns (arrays path) and with literals (scalars path)
+    //! to make sure that both paths order NaN the same way
+
+    use crate::tests::utils::{create_context_with_udf, create_empty_data_frame, create_primitive_array, get_combined_results, get_list_result_as_matrix, get_primitive_result_as_matrix, parse_primitive_column};
+    use crate::{GreatestUdf, LeastUdf, NanOrdering};
+    use datafusion::arrow::array::{ArrayRef, DictionaryArray, Int32Array, ListArray, RecordBatch};
+    use datafusion::arrow::compute::cast;
+    use datafusion::arrow::datatypes::{DataType, Float64Type};
+    use datafusion::prelude::SessionContext;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{col, lit, ScalarUDF};
+    use std::sync::Arc;
+
+    fn a_values() -> Vec<Option<f64>> {
+        vec![Some(f64::NAN), Some(1.0), Some(f64::NAN), Some(-f64::NAN), Some(-0.0), Some(0.0), Some(f64::INFINITY), Some(f64::NEG_INFINITY), Some(f64::NAN), None]
+    }
+
+    fn b_values() -> Vec<Option<f64>> {
+        vec![Some(1.0), Some(f64::NAN), Some(f64::NAN), Some(f64::INFINITY), Some(0.0), Some(-0.0), Some(f64::NAN), Some(f64::NAN), None, Some(f64::NAN)]
+    }
+
+    /// NaN is not equal to itself, so compare the debug representation instead (which also show the sign of zero)
+    fn to_debug<T: std::fmt::Debug>(values: Vec<T>) -> Vec<String> {
+        values.iter().map(|value| format!("{:?}", value)).collect()
+    }
+
+    async fn assert_arrays_and_scalars(udf: ScalarUDF, expected: Vec<Option<f64>>) {
+        let (ctx, udf) = create_context_with_udf(udf);
+
+        let a: ArrayRef = create_primitive_array::<Float64Type>(a_values());
+        let b: ArrayRef = create_primitive_array::<Float64Type>(b_values());
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![udf.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Float64Type>(df).await.unwrap();
+
+        assert_eq!(to_debug(results[0].clone()), to_debug(expected.clone()), "arrays path");
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let df = df.select(
+            a_values()
+                .into_iter()
+                .zip(b_values())
+                .map(|(a, b)| udf.call(vec![lit(ScalarValue::Float64(a)), lit(ScalarValue::Float64(b))]))
+                .collect::<Vec<_>>()
+        ).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Float64Type>(df).await.unwrap();
+
+        assert_eq!(to_debug(results.concat()), to_debug(expected.clone()), "scalars path");
+
+        assert_dictionaries(&ctx, &udf, expected).await;
+    }
+
+    fn to_dictionary(values: Vec<Option<f64>>) -> ArrayRef {
+        let keys = Int32Array::from_iter((0..values.len() as i32).map(|i| Some(i).filter(|&i| values[i as usize].is_some())));
+
+        Arc::new(DictionaryArray::new(keys, create_primitive_array::<Float64Type>(values)))
+    }
+
+    fn to_dictionary_scalar(value: Option<f64>) -> ScalarValue {
+        ScalarValue::Dictionary(Box::new(DataType::Int32), Box::new(ScalarValue::Float64(value)))
+    }
+
+    /// Dictionaries of floats should order NaN the same way as the floats
+    async fn assert_dictionaries(ctx: &SessionContext, udf: &ScalarUDF, expected: Vec<Option<f64>>) {
+        let batch = RecordBatch::try_from_iter(vec![("a", to_dictionary(a_values())), ("b", to_dictionary(b_values()))]).unwrap();
+
+        ctx.register_batch("dictionaries", batch).unwrap();
+
+        let df = ctx.table("dictionaries").await.unwrap();
+
+        let df = df.select(vec![udf.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+        let results = cast(results.column(0), &DataType::Float64).unwrap();
+
+        assert_eq!(to_debug(parse_primitive_column::<Float64Type>(&results)), to_debug(expected.clone()), "dictionaries arrays path");
+
+        let df = create_empty_data_frame(ctx, true).unwrap();
+
+        let df = df.select(
+            a_values()
+                .into_iter()
+                .zip(b_values())
+                .map(|(a, b)| udf.call(vec![lit(to_dictionary_scalar(a)), lit(to_dictionary_scalar(b))]))
+                .collect::<Vec<_>>()
+        ).unwrap();
+
+        let results = get_combined_results(df).await.unwrap();
+        let results = results
+            .columns()
+            .iter()
+            .flat_map(|column| parse_primitive_column::<Float64Type>(&cast(column, &DataType::Float64).unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(to_debug(results), to_debug(expected), "dictionaries scalars path");
+    }
+
+    #[tokio::test]
+    async fn greatest_nan_is_greatest() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Greatest)),
+            vec![Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(0.0), Some(0.0), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    #[tokio::test]
+    async fn least_nan_is_greatest() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(LeastUdf::new().with_nan_ordering(NanOrdering::Greatest)),
+            vec![Some(1.0), Some(1.0), Some(f64::NAN), Some(f64::INFINITY), Some(-0.0), Some(-0.0), Some(f64::INFINITY), Some(f64::NEG_INFINITY), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    #[tokio::test]
+    async fn greatest_nan_is_skipped() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Skip)),
+            vec![Some(1.0), Some(1.0), Some(f64::NAN), Some(f64::INFINITY), Some(0.0), Some(0.0), Some(f64::INFINITY), Some(f64::NEG_INFINITY), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    #[tokio::test]
+    async fn least_nan_is_skipped() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(LeastUdf::new().with_nan_ordering(NanOrdering::Skip)),
+            vec![Some(1.0), Some(1.0), Some(f64::NAN), Some(f64::INFINITY), Some(-0.0), Some(-0.0), Some(f64::INFINITY), Some(f64::NEG_INFINITY), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    #[tokio::test]
+    async fn greatest_nan_is_propagated() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Propagate)),
+            vec![Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(0.0), Some(0.0), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    #[tokio::test]
+    async fn least_nan_is_propagated() {
+        assert_arrays_and_scalars(
+            ScalarUDF::from(LeastUdf::new().with_nan_ordering(NanOrdering::Propagate)),
+            vec![Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(-0.0), Some(-0.0), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN), Some(f64::NAN)],
+        ).await;
+    }
+
+    async fn get_lists_result(udf: ScalarUDF) -> Vec<String> {
+        let (ctx, udf) = create_context_with_udf(udf);
+
+        let a_vec = vec![
+            Some(vec![Some(f64::NAN)]),
+            Some(vec![Some(1.0), Some(f64::NAN)]),
+            Some(vec![Some(f64::NAN)]),
+            Some(vec![Some(-f64::NAN)]),
+        ];
+        let b_vec = vec![
+            Some(vec![Some(1.0)]),
+            Some(vec![Some(1.0), Some(2.0)]),
+            Some(vec![Some(f64::INFINITY)]),
+            Some(vec![Some(f64::NAN)]),
+        ];
+        let a: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Float64Type, _, _>(a_vec));
+        let b: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Float64Type, _, _>(b_vec));
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![udf.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Float64Type>(df).await.unwrap();
+
+        to_debug(results[0].clone())
+    }
+
+    #[tokio::test]
+    async fn lists_nan_is_greatest() {
+        assert_eq!(
+            get_lists_result(ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Greatest))).await,
+            to_debug(vec![
+                Some(vec![Some(f64::NAN)]),
+                Some(vec![Some(1.0), Some(f64::NAN)]),
+                Some(vec![Some(f64::NAN)]),
+                // Negative NaN is equal to NaN
+                Some(vec![Some(-f64::NAN)]),
+            ]),
+        );
+
+        assert_eq!(
+            get_lists_result(ScalarUDF::from(LeastUdf::new().with_nan_ordering(NanOrdering::Greatest))).await,
+            to_debug(vec![
+                Some(vec![Some(1.0)]),
+                Some(vec![Some(1.0), Some(2.0)]),
+                Some(vec![Some(f64::INFINITY)]),
+                Some(vec![Some(-f64::NAN)]),
+            ]),
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_nan_is_skipped() {
+        // NaN list items are ordered like null list items, which are smaller than any value
+        assert_eq!(
+            get_lists_result(ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Skip))).await,
+            to_debug(vec![
+                Some(vec![Some(1.0)]),
+                Some(vec![Some(1.0), Some(2.0)]),
+                Some(vec![Some(f64::INFINITY)]),
+                Some(vec![Some(-f64::NAN)]),
+            ]),
+        );
+    }
+}
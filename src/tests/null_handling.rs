@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod null_handling_tests {
+    use crate::helpers::{Permutation, Transpose};
+    use crate::tests::utils::{create_context_with_udf, create_empty_data_frame, create_primitive_array, get_list_result_as_matrix, get_primitive_result_as_matrix};
+    use crate::{vec_with_lit, GreatestUdf, LeastUdf, NullHandling};
+    use datafusion::arrow::array::{ArrayRef, ListArray, RecordBatch};
+    use datafusion::arrow::datatypes::{Int32Type, Int8Type};
+    use datafusion::prelude::SessionContext;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{col, lit, ScalarUDF};
+    use std::sync::Arc;
+
+    fn create_propagate_context() -> (SessionContext, ScalarUDF) {
+        create_context_with_udf(ScalarUDF::from(GreatestUdf::new().with_null_handling(NullHandling::Propagate)))
+    }
+
+    #[tokio::test]
+    async fn i8_arrays_with_nulls() {
+        let (ctx, greatest) = create_propagate_context();
+
+        let a_vec = vec![Some(1), None, Some(-8), Some(126), None];
+        let b_vec = vec![Some(3), Some(-23), None, Some(3), None];
+        let a: ArrayRef = create_primitive_array::<Int8Type>(a_vec.clone());
+        let b: ArrayRef = create_primitive_array::<Int8Type>(b_vec.clone());
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(3), None, None, Some(126), None]
+        ]);
+    }
+
+    #[tokio::test]
+    async fn null_scalar_with_arrays() {
+        let (ctx, greatest) = create_propagate_context();
+
+        let a_vec = vec![Some(1), Some(4), Some(-8), Some(126)];
+        let b_vec = vec![Some(3), Some(-23), Some(34), Some(3)];
+        let a: ArrayRef = create_primitive_array::<Int8Type>(a_vec.clone());
+        let b: ArrayRef = create_primitive_array::<Int8Type>(b_vec.clone());
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let greatest_args = vec![col("a"), col("b"), lit(5i8), lit(ScalarValue::Int8(None))];
+
+        for args in greatest_args.permutation(greatest_args.len()) {
+            let df = df.clone().select(vec![
+                greatest.call(args)
+            ]).unwrap();
+
+            let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap();
+
+            assert_eq!(results, vec![
+                vec![None; a_vec.len()]
+            ]);
+        }
+    }
+
+    #[tokio::test]
+    async fn scalars() {
+        let (ctx, greatest) = create_propagate_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let with_null = vec_with_lit![1i8, ScalarValue::Null, 5i8].permutation(3);
+        let without_null = vec_with_lit![1i8, 3i8, 5i8].permutation(3);
+
+        let df = df.select([
+            with_null.iter().map(|v| greatest.call(v.clone())).collect::<Vec<_>>(),
+            without_null.iter().map(|v| greatest.call(v.clone())).collect::<Vec<_>>(),
+        ].concat()).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap().transpose();
+
+        assert_eq!(results, vec![[
+            vec![None; with_null.len()],
+            vec![Some(5); without_null.len()],
+        ].concat()]);
+    }
+
+    #[tokio::test]
+    async fn lists() {
+        let (ctx, greatest) = create_propagate_context();
+
+        let a_vec = vec![
+            Some(vec![Some(2), Some(100)]),
+            None,
+            Some(vec![None, Some(100)]),
+        ];
+        let b_vec = vec![
+            None,
+            Some(vec![Some(1), Some(200)]),
+            Some(vec![Some(1), Some(200)]),
+        ];
+        let a: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(a_vec));
+        let b: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(b_vec));
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![
+            None,
+            None,
+            // Only top level nulls are propagated, null list items are still smaller than any value
+            Some(vec![Some(1), Some(200)]),
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn least_propagates_nulls() {
+        let (ctx, least) = create_context_with_udf(ScalarUDF::from(LeastUdf::new().with_null_handling(NullHandling::Propagate)));
+
+        let a_vec = vec![Some(1), None, Some(-8), Some(126)];
+        let b_vec = vec![Some(3), Some(-23), None, Some(3)];
+        let a: ArrayRef = create_primitive_array::<Int8Type>(a_vec.clone());
+        let b: ArrayRef = create_primitive_array::<Int8Type>(b_vec.clone());
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![least.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int8Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(1), None, None, Some(3)]
+        ]);
+    }
+
+    #[test]
+    fn udfs_with_different_null_handling_are_not_equal() {
+        let skip = ScalarUDF::from(GreatestUdf::new());
+        let propagate = ScalarUDF::from(GreatestUdf::new().with_null_handling(NullHandling::Propagate));
+
+        assert_eq!(skip, ScalarUDF::from(GreatestUdf::new()));
+        assert_ne!(skip, propagate);
+    }
+}
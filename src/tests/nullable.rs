@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod nullable_tests {
+    use crate::tests::utils::{create_context_with_udf, get_combined_results};
+    use crate::{GreatestUdf, LeastUdf, NullHandling};
+    use datafusion::arrow::array::{ArrayRef, Int32Array, RecordBatch};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_expr::{col, ExprSchemable, ScalarUDF};
+    use std::sync::Arc;
+
+    #[test]
+    fn skip_nulls_is_nullable_only_if_all_arguments_are() {
+        let greatest = GreatestUdf::new().with_null_handling(NullHandling::Skip);
+        let least = LeastUdf::new().with_null_handling(NullHandling::Skip);
+
+        assert!(greatest.nullable(&[true, true]));
+        assert!(least.nullable(&[true, true]));
+
+        for args_nullable in [[true, false], [false, true], [false, false]] {
+            assert!(!greatest.nullable(&args_nullable), "{:?}", args_nullable);
+            assert!(!least.nullable(&args_nullable), "{:?}", args_nullable);
+        }
+    }
+
+    #[test]
+    fn propagate_nulls_is_nullable_if_any_argument_is() {
+        let greatest = GreatestUdf::new().with_null_handling(NullHandling::Propagate);
+        let least = LeastUdf::new().with_null_handling(NullHandling::Propagate);
+
+        for args_nullable in [[true, true], [true, false], [false, true]] {
+            assert!(greatest.nullable(&args_nullable), "{:?}", args_nullable);
+            assert!(least.nullable(&args_nullable), "{:?}", args_nullable);
+        }
+
+        assert!(!greatest.nullable(&[false, false]));
+        assert!(!least.nullable(&[false, false]));
+    }
+
+    #[tokio::test]
+    async fn output_field_from_the_arguments_schema() {
+        let udf = GreatestUdf::new();
+        let (ctx, greatest) = create_context_with_udf(ScalarUDF::from(udf.clone()));
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, false),
+        ]));
+
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(5)])) as ArrayRef,
+                Arc::new(Int32Array::from(vec![2, 3, 4])) as ArrayRef,
+            ],
+        ).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+        let args = vec![col("a"), col("b")];
+
+        let args_nullable = args.iter().map(|arg| arg.nullable(df.schema()).unwrap()).collect::<Vec<_>>();
+
+        // DataFusion does not ask the function about its nullability, so the output field is nullable
+        let df = df.select(vec![greatest.call(args)]).unwrap();
+
+        assert!(df.schema().field(0).is_nullable());
+
+        // The field built from the arguments is non-nullable and matches the results
+        let field = Field::new("greatest", DataType::Int32, udf.nullable(&args_nullable));
+
+        assert!(!field.is_nullable());
+
+        let results = get_combined_results(df).await.unwrap();
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(vec![field])), vec![results.column(0).clone()]).unwrap();
+
+        assert!(!batch.schema().field(0).is_nullable());
+        assert_eq!(batch.column(0).null_count(), 0);
+    }
+}
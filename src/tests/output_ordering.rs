@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod output_ordering_tests {
+    //! These tests check that sorts on greatest and least are removed only when the result is already sorted
+
+    use crate::tests::utils::get_primitive_result_as_matrix;
+    use crate::{register_all, GreatestUdf, LeastUdf, NanOrdering, NullHandling};
+    use datafusion::arrow::array::{ArrayRef, Float64Array, Int32Array, RecordBatch};
+    use datafusion::arrow::datatypes::{Float64Type, Int32Type, Int64Type};
+    use datafusion::dataframe::DataFrame;
+    use datafusion::datasource::MemTable;
+    use datafusion::physical_plan::displayable;
+    use datafusion::prelude::{SessionConfig, SessionContext};
+    use datafusion_expr::{col, Expr, ScalarUDF};
+    use std::sync::Arc;
+
+    /// Create a context with a single partition table `t` where `a` and `b` are sorted ascending with nulls first
+    /// and `d` is not sorted, and a table `desc_t` where `c` is sorted descending with nulls last
+    fn create_sorted_context() -> SessionContext {
+        let ctx = SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1));
+        register_all(&ctx);
+
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![None, Some(1), Some(2), Some(6)]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![None, Some(4), Some(4), Some(5)]));
+        let d: ArrayRef = Arc::new(Int32Array::from(vec![Some(3), Some(7), Some(1), Some(2)]));
+
+        register_sorted_table(
+            &ctx,
+            "t",
+            vec![("a", a), ("b", b), ("d", d)],
+            vec![
+                vec![col("a").sort(true, true)],
+                vec![col("b").sort(true, true)],
+            ],
+        );
+
+        let c: ArrayRef = Arc::new(Int32Array::from(vec![Some(9), Some(3), Some(1), None]));
+
+        register_sorted_table(
+            &ctx,
+            "desc_t",
+            vec![("c", c)],
+            vec![vec![col("c").sort(false, false)]],
+        );
+
+        ctx
+    }
+
+    fn register_sorted_table(
+        ctx: &SessionContext,
+        name: &str,
+        columns: Vec<(&str, ArrayRef)>,
+        sort_order: Vec<Vec<Expr>>,
+    ) {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+
+        let table = MemTable::try_new(batch.schema(), vec![vec![batch]])
+            .unwrap()
+            .with_sort_order(sort_order);
+
+        ctx.register_table(name, Arc::new(table)).unwrap();
+    }
+
+    async fn has_sort(df: DataFrame) -> bool {
+        let plan = df.create_physical_plan().await.unwrap();
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+
+        plan.contains("SortExec")
+    }
+
+    async fn plan_has_sort(ctx: &SessionContext, sql: &str) -> bool {
+        has_sort(ctx.sql(sql).await.unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn remove_sort_when_all_inputs_are_sorted() {
+        let ctx = create_sorted_context();
+
+        assert!(
+            !plan_has_sort(
+                &ctx,
+                "SELECT greatest(a, b) AS g FROM t ORDER BY g ASC NULLS FIRST"
+            )
+            .await
+        );
+
+        // The constants are ignored
+        assert!(
+            !plan_has_sort(
+                &ctx,
+                "SELECT greatest(a, 3, b) AS g FROM t ORDER BY g ASC NULLS FIRST"
+            )
+            .await
+        );
+
+        // Descending with nulls last sort NULL where greatest put them as well
+        assert!(
+            !plan_has_sort(
+                &ctx,
+                "SELECT greatest(c, 3) AS g FROM desc_t ORDER BY g DESC NULLS LAST"
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_sort_when_not_all_inputs_are_sorted() {
+        let ctx = create_sorted_context();
+
+        // only the first argument is sorted
+        assert!(
+            plan_has_sort(
+                &ctx,
+                "SELECT greatest(a, d) AS g FROM t ORDER BY g ASC NULLS FIRST"
+            )
+            .await
+        );
+
+        // only the second argument is sorted
+        assert!(
+            plan_has_sort(
+                &ctx,
+                "SELECT greatest(d, a) AS g FROM t ORDER BY g ASC NULLS FIRST"
+            )
+            .await
+        );
+
+        // arguments are sorted in different directions
+        assert!(
+            plan_has_sort(
+                &ctx,
+                "SELECT greatest(a, c) AS g FROM t CROSS JOIN desc_t ORDER BY g ASC NULLS FIRST"
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_sort_when_nulls_are_not_sorted_as_compared() {
+        let ctx = create_sorted_context();
+
+        // NULL is sorted first but least skips it, so it is kept only when all the values are NULL
+        assert!(
+            plan_has_sort(
+                &ctx,
+                "SELECT least(a, b) AS l FROM t ORDER BY l ASC NULLS FIRST"
+            )
+            .await
+        );
+        assert!(
+            plan_has_sort(
+                &ctx,
+                "SELECT least(c, 3) AS l FROM desc_t ORDER BY l DESC NULLS LAST"
+            )
+            .await
+        );
+
+        // Unless NULL is propagated
+        let least_propagate =
+            ScalarUDF::from(LeastUdf::new().with_null_handling(NullHandling::Propagate));
+        let df = ctx
+            .table("t")
+            .await
+            .unwrap()
+            .select(vec![least_propagate
+                .call(vec![col("a"), col("b")])
+                .alias("l")])
+            .unwrap()
+            .sort(vec![col("l").sort(true, true)])
+            .unwrap();
+
+        assert!(!has_sort(df).await);
+    }
+
+    #[tokio::test]
+    async fn keep_sort_for_floating_point() {
+        let ctx = create_sorted_context();
+
+        // -NaN is sorted first and NaN is sorted last, while greatest and least compare them the same
+        let x: ArrayRef = Arc::new(Float64Array::from(vec![-f64::NAN, 0.0]));
+        let y: ArrayRef = Arc::new(Float64Array::from(vec![5.0, 6.0]));
+
+        register_sorted_table(
+            &ctx,
+            "f",
+            vec![("x", x), ("y", y)],
+            vec![
+                vec![col("x").sort(true, true)],
+                vec![col("y").sort(true, true)],
+            ],
+        );
+
+        for nan_ordering in [NanOrdering::Greatest, NanOrdering::Skip, NanOrdering::Propagate] {
+            let greatest = ScalarUDF::from(GreatestUdf::new().with_nan_ordering(nan_ordering));
+            let least = ScalarUDF::from(LeastUdf::new().with_nan_ordering(nan_ordering));
+
+            for udf in [greatest, least] {
+                let df = ctx
+                    .table("f")
+                    .await
+                    .unwrap()
+                    .select(vec![udf.call(vec![col("x"), col("y")]).alias("g")])
+                    .unwrap()
+                    .sort(vec![col("g").sort(true, true)])
+                    .unwrap();
+
+                assert!(has_sort(df).await, "{} with {:?}", udf.name(), nan_ordering);
+            }
+        }
+
+        // The inputs are sorted but least is not, as -NaN is the greatest value
+        let df = ctx
+            .sql("SELECT least(x, y) AS l FROM f ORDER BY l ASC NULLS FIRST")
+            .await
+            .unwrap();
+
+        let results = get_primitive_result_as_matrix::<Float64Type>(df)
+            .await
+            .unwrap();
+
+        assert_eq!(results, vec![vec![Some(0.0), Some(5.0)]]);
+
+        // Integers have no NaN, so the NaN ordering does not matter
+        let greatest = ScalarUDF::from(GreatestUdf::new().with_nan_ordering(NanOrdering::Skip));
+        let df = ctx
+            .table("t")
+            .await
+            .unwrap()
+            .select(vec![greatest.call(vec![col("a"), col("b")]).alias("g")])
+            .unwrap()
+            .sort(vec![col("g").sort(true, true)])
+            .unwrap();
+
+        assert!(!has_sort(df).await);
+    }
+
+    #[tokio::test]
+    async fn results_are_sorted() {
+        let ctx = create_sorted_context();
+
+        let df = ctx
+            .sql("SELECT greatest(a, 3, b) AS g FROM t ORDER BY g ASC NULLS FIRST")
+            .await
+            .unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int64Type>(df)
+            .await
+            .unwrap();
+
+        assert_eq!(results, vec![vec![Some(3), Some(4), Some(4), Some(6)]]);
+
+        let df = ctx
+            .sql("SELECT greatest(a, d) AS g FROM t ORDER BY g ASC NULLS FIRST")
+            .await
+            .unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df)
+            .await
+            .unwrap();
+
+        assert_eq!(results, vec![vec![Some(2), Some(3), Some(6), Some(7)]]);
+    }
+}
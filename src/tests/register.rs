@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod register_tests {
+    use crate::tests::utils::get_primitive_result_as_matrix;
+    use crate::{functions, register_all, register_all_with_registry};
+    use datafusion::arrow::datatypes::Int64Type;
+    use datafusion::execution::FunctionRegistry;
+    use datafusion::prelude::SessionContext;
+
+    #[test]
+    fn functions_have_unique_names() {
+        let mut names = functions().iter().map(|udf| udf.name().to_string()).collect::<Vec<_>>();
+        let count = names.len();
+
+        names.sort();
+        names.dedup();
+
+        assert_eq!(names.len(), count, "Function names must be unique: {:?}", names);
+    }
+
+    #[tokio::test]
+    async fn register_all_on_session_context() {
+        let ctx = SessionContext::new();
+
+        register_all(&ctx);
+
+        for udf in functions() {
+            assert!(ctx.udf(udf.name()).is_ok(), "{} should be registered", udf.name());
+        }
+
+        let df = ctx.sql("SELECT greatest(1, 5, 3), least(1, 5, 3)").await.unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(5)], vec![Some(1)]]);
+    }
+
+    #[tokio::test]
+    async fn register_all_on_function_registry() {
+        let mut state = SessionContext::new().state();
+
+        register_all_with_registry(&mut state).unwrap();
+
+        for udf in functions() {
+            assert!(state.udf(udf.name()).is_ok(), "{} should be registered", udf.name());
+        }
+
+        let ctx = SessionContext::new_with_state(state);
+
+        let df = ctx.sql("SELECT greatest(1, 5, 3), least(1, 5, 3)").await.unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(5)], vec![Some(1)]]);
+    }
+}
@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod rewrite_greatest_predicate_tests {
+    //! These tests check that filter predicates on greatest are rewritten into predicates on its arguments,
+    //! and that the rewritten plans return the same results
+
+    use crate::tests::utils::{create_primitive_array, get_combined_results};
+    use crate::{
+        register_all_with_registry, GreatestUdf, LeastUdf, NullHandling, RewriteGreatestPredicate,
+    };
+    use datafusion::arrow::array::{ArrayRef, RecordBatch};
+    use datafusion::arrow::datatypes::{DataType, Field, Int32Type, Schema};
+    use datafusion::arrow::util::pretty::pretty_format_batches;
+    use datafusion::execution::session_state::SessionStateBuilder;
+    use datafusion::execution::FunctionRegistry;
+    use datafusion::prelude::SessionContext;
+    use datafusion_expr::expr_rewriter::unnormalize_col;
+    use datafusion_expr::logical_plan::table_scan;
+    use datafusion_expr::{col, lit, not, Expr, LogicalPlan, LogicalPlanBuilder, ScalarUDF};
+    use datafusion_optimizer::{OptimizerContext, OptimizerRule};
+    use std::sync::Arc;
+
+    /// Filter the table `t` where `a` and `b` are non nullable and `x` and `y` are nullable by the predicate,
+    /// and return the predicate after running the rule
+    fn rewrite(predicate: Expr) -> Expr {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+            Field::new("x", DataType::Int32, true),
+            Field::new("y", DataType::Int32, true),
+            Field::new("f", DataType::Float64, false),
+        ]);
+
+        let plan = LogicalPlanBuilder::from(
+            table_scan(Some("t"), &schema, None)
+                .unwrap()
+                .build()
+                .unwrap(),
+        )
+        .filter(predicate)
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let plan = RewriteGreatestPredicate::new()
+            .rewrite(plan, &OptimizerContext::new())
+            .unwrap()
+            .data;
+
+        let LogicalPlan::Filter(filter) = plan else {
+            panic!("Expected filter, got {}", plan.display_indent());
+        };
+
+        // Remove the table qualifier added by the plan builder
+        unnormalize_col(filter.predicate)
+    }
+
+    fn greatest() -> ScalarUDF {
+        ScalarUDF::from(GreatestUdf::new())
+    }
+
+    fn greatest_propagate() -> ScalarUDF {
+        ScalarUDF::from(GreatestUdf::new().with_null_handling(NullHandling::Propagate))
+    }
+
+    #[test]
+    fn rewrite_non_nullable_arguments() {
+        let args = vec![col("a"), col("b")];
+
+        assert_eq!(
+            rewrite(greatest().call(args.clone()).gt(lit(5))),
+            col("a").gt(lit(5)).or(col("b").gt(lit(5)))
+        );
+        assert_eq!(
+            rewrite(greatest().call(args.clone()).gt_eq(lit(5))),
+            col("a").gt_eq(lit(5)).or(col("b").gt_eq(lit(5)))
+        );
+        assert_eq!(
+            rewrite(greatest().call(args.clone()).lt(lit(5))),
+            col("a").lt(lit(5)).and(col("b").lt(lit(5)))
+        );
+        assert_eq!(
+            rewrite(greatest().call(args.clone()).lt_eq(lit(5))),
+            col("a").lt_eq(lit(5)).and(col("b").lt_eq(lit(5)))
+        );
+        assert_eq!(
+            rewrite(greatest().call(args.clone()).eq(lit(5))),
+            col("a")
+                .lt_eq(lit(5))
+                .and(col("b").lt_eq(lit(5)))
+                .and(col("a").eq(lit(5)).or(col("b").eq(lit(5))))
+        );
+    }
+
+    #[test]
+    fn rewrite_when_greatest_is_on_the_right() {
+        assert_eq!(
+            rewrite(lit(5).lt(greatest().call(vec![col("a"), col("b")]))),
+            col("a").gt(lit(5)).or(col("b").gt(lit(5)))
+        );
+    }
+
+    #[test]
+    fn rewrite_nullable_arguments_when_skipping_nulls() {
+        assert_eq!(
+            rewrite(greatest().call(vec![col("x"), col("y")]).gt(lit(5))),
+            col("x").gt(lit(5)).or(col("y").gt(lit(5)))
+        );
+
+        // NULL argument is skipped, but the result is NULL when all the arguments are NULL
+        assert_eq!(
+            rewrite(greatest().call(vec![col("x"), col("y")]).lt(lit(5))),
+            col("x")
+                .lt(lit(5))
+                .or(col("x").is_null())
+                .and(col("y").lt(lit(5)).or(col("y").is_null()))
+                .and(col("x").is_not_null().or(col("y").is_not_null()))
+        );
+
+        // The result can't be NULL when one of the arguments is not nullable
+        assert_eq!(
+            rewrite(greatest().call(vec![col("x"), col("a")]).lt(lit(5))),
+            col("x")
+                .lt(lit(5))
+                .or(col("x").is_null())
+                .and(col("a").lt(lit(5)))
+        );
+    }
+
+    #[test]
+    fn rewrite_nullable_arguments_when_propagating_nulls() {
+        assert_eq!(
+            rewrite(
+                greatest_propagate()
+                    .call(vec![col("x"), col("a")])
+                    .gt(lit(5))
+            ),
+            col("x")
+                .gt(lit(5))
+                .or(col("a").gt(lit(5)))
+                .and(col("x").is_not_null())
+        );
+        assert_eq!(
+            rewrite(
+                greatest_propagate()
+                    .call(vec![col("x"), col("y")])
+                    .lt(lit(5))
+            ),
+            col("x").lt(lit(5)).and(col("y").lt(lit(5)))
+        );
+    }
+
+    #[test]
+    fn rewrite_predicates_combined_with_and_or() {
+        let predicate = col("a").eq(lit(1)).and(
+            greatest()
+                .call(vec![col("a"), col("b")])
+                .gt(lit(5))
+                .or(col("b").eq(lit(2))),
+        );
+
+        assert_eq!(
+            rewrite(predicate),
+            col("a").eq(lit(1)).and(
+                col("a")
+                    .gt(lit(5))
+                    .or(col("b").gt(lit(5)))
+                    .or(col("b").eq(lit(2)))
+            )
+        );
+    }
+
+    #[test]
+    fn do_not_rewrite_unsafe_predicates() {
+        let predicates = vec![
+            // NULL predicate is not filtered out when negated
+            not(greatest().call(vec![col("x"), col("y")]).gt(lit(5))),
+            // NaN is ordered differently
+            greatest().call(vec![col("f"), lit(1.0)]).gt(lit(5.0)),
+            // Not greatest
+            ScalarUDF::from(LeastUdf::new())
+                .call(vec![col("a"), col("b")])
+                .gt(lit(5)),
+            // Not supported operator
+            greatest().call(vec![col("a"), col("b")]).not_eq(lit(5)),
+        ];
+
+        for predicate in predicates {
+            assert_eq!(rewrite(predicate.clone()), predicate);
+        }
+    }
+
+    fn create_context(
+        with_rule: bool,
+        null_handling: NullHandling,
+        batch: RecordBatch,
+    ) -> SessionContext {
+        let mut builder = SessionStateBuilder::new().with_default_features();
+
+        if with_rule {
+            builder = builder.with_optimizer_rule(Arc::new(RewriteGreatestPredicate::new()));
+        }
+
+        let mut state = builder.build();
+        register_all_with_registry(&mut state).unwrap();
+
+        // Replace greatest with the one with the requested null handling
+        state
+            .register_udf(Arc::new(ScalarUDF::from(
+                GreatestUdf::new().with_null_handling(null_handling),
+            )))
+            .unwrap();
+
+        let ctx = SessionContext::new_with_state(state);
+
+        ctx.register_batch("t", batch).unwrap();
+
+        ctx
+    }
+
+    #[tokio::test]
+    async fn rewritten_plan_return_same_results() {
+        // Every combination of the values and NULL, so every predicate matches some rows
+        let values = [None]
+            .into_iter()
+            .chain((0..10).map(Some))
+            .collect::<Vec<_>>();
+        let mut rows = vec![];
+
+        for &x in &values {
+            for &y in &values {
+                for &z in &values {
+                    rows.push([x, y, z]);
+                }
+            }
+        }
+
+        let columns = ["x", "y", "z"].into_iter().enumerate().map(|(i, name)| {
+            (
+                name,
+                create_primitive_array::<Int32Type>(rows.iter().map(|row| row[i]).collect())
+                    as ArrayRef,
+            )
+        });
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+
+        for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+            let with_rule = create_context(true, null_handling, batch.clone());
+            let without_rule = create_context(false, null_handling, batch.clone());
+
+            for op in [">", ">=", "<", "<=", "="] {
+                let sql = format!(
+                    "SELECT x, y, z FROM t WHERE greatest(x, y, z) {op} 4 ORDER BY x, y, z"
+                );
+
+                let plan = with_rule.sql(&format!("EXPLAIN {sql}")).await.unwrap();
+                let plan = pretty_format_batches(&[get_combined_results(plan).await.unwrap()])
+                    .unwrap()
+                    .to_string();
+                let filter = plan.lines().find(|line| line.contains("Filter:")).unwrap();
+
+                assert!(
+                    !filter.contains("greatest"),
+                    "{sql} was not rewritten with {null_handling:?}: {filter}"
+                );
+
+                let expected = get_combined_results(without_rule.sql(&sql).await.unwrap())
+                    .await
+                    .unwrap();
+                let actual = get_combined_results(with_rule.sql(&sql).await.unwrap())
+                    .await
+                    .unwrap();
+
+                assert!(
+                    expected.num_rows() > 0,
+                    "{sql} should return some rows with {null_handling:?}"
+                );
+                assert_eq!(actual, expected, "{sql} with {null_handling:?}");
+            }
+        }
+    }
+}
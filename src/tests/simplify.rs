@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod simplify_tests {
+    //! These tests check the logical simplification of greatest calls,
+    //! and that the simplified plans return the same results
+
+    use crate::tests::utils::{create_context, get_primitive_result_as_matrix};
+    use crate::{GreatestUdf, LeastUdf, NullHandling};
+    use datafusion::arrow::array::{ArrayRef, Int32Array, RecordBatch};
+    use datafusion::arrow::datatypes::{DataType, Field, Int32Type, Schema};
+    use datafusion::execution::context::ExecutionProps;
+    use datafusion::functions::expr_fn::random;
+    use datafusion::optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+    use datafusion_common::{DFSchema, ScalarValue};
+    use datafusion_expr::{cast, col, lit, Expr, ExprSchemable, ScalarUDF};
+    use std::sync::Arc;
+
+    fn create_schema() -> Arc<DFSchema> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+            Field::new("c", DataType::Int32, true),
+            Field::new("d", DataType::Int64, true),
+        ]);
+
+        Arc::new(DFSchema::try_from(schema).unwrap())
+    }
+
+    fn simplify(expr: Expr) -> Expr {
+        let props = ExecutionProps::new();
+
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(create_schema()))
+            .simplify(expr)
+            .unwrap()
+    }
+
+    fn null_i32() -> Expr {
+        lit(ScalarValue::Int32(None))
+    }
+
+    #[test]
+    fn flatten_nested_calls() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+
+        let expr = greatest.call(vec![
+            greatest.call(vec![col("a"), greatest.call(vec![col("b"), col("c")])]),
+            col("a"),
+            lit(4),
+        ]);
+
+        assert_eq!(simplify(expr), greatest.call(vec![col("a"), col("b"), col("c"), lit(4)]));
+    }
+
+    #[test]
+    fn do_not_flatten_different_functions() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+        let least = ScalarUDF::from(LeastUdf::new());
+        let greatest_propagate = ScalarUDF::from(GreatestUdf::new().with_null_handling(NullHandling::Propagate));
+
+        let expr = greatest.call(vec![
+            least.call(vec![col("a"), col("b")]),
+            greatest_propagate.call(vec![col("a"), col("b")]),
+        ]);
+
+        assert_eq!(simplify(expr.clone()), expr);
+    }
+
+    #[test]
+    fn remove_null_literals() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+
+        let expr = greatest.call(vec![col("a"), null_i32(), col("b"), lit(ScalarValue::Null)]);
+
+        assert_eq!(simplify(expr), greatest.call(vec![col("a"), col("b")]));
+
+        let expr = greatest.call(vec![null_i32(), null_i32()]);
+
+        assert_eq!(simplify(expr), null_i32());
+    }
+
+    #[test]
+    fn null_literals_return_null_when_propagating_nulls() {
+        let greatest = ScalarUDF::from(GreatestUdf::new().with_null_handling(NullHandling::Propagate));
+
+        let expr = greatest.call(vec![col("a"), lit(5), null_i32(), col("b")]);
+
+        assert_eq!(simplify(expr), null_i32());
+    }
+
+    #[test]
+    fn fold_literals() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+        let least = ScalarUDF::from(LeastUdf::new());
+
+        let expr = greatest.call(vec![col("a"), lit(1), col("b"), lit(5), lit(3)]);
+
+        assert_eq!(simplify(expr), greatest.call(vec![col("a"), lit(5), col("b")]));
+
+        let expr = least.call(vec![col("a"), lit(1), col("b"), lit(5), lit(3)]);
+
+        // least is not simplified
+        assert_eq!(simplify(expr.clone()), expr);
+    }
+
+    #[test]
+    fn keep_the_type_of_removed_arguments() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+        let schema = create_schema();
+
+        let cases = vec![
+            (
+                greatest.call(vec![col("a"), lit(ScalarValue::Int64(None)), col("b")]),
+                cast(greatest.call(vec![col("a"), col("b")]), DataType::Int64),
+            ),
+            (
+                greatest.call(vec![col("a"), lit(ScalarValue::Int64(None)), col("a")]),
+                cast(col("a"), DataType::Int64),
+            ),
+            (
+                greatest.call(vec![col("a"), col("d"), col("b"), col("d")]),
+                greatest.call(vec![col("a"), col("d"), col("b")]),
+            ),
+        ];
+
+        for (expr, expected) in cases {
+            let simplified = simplify(expr.clone());
+
+            assert_eq!(simplified, expected);
+            assert_eq!(simplified.get_type(&schema).unwrap(), expr.get_type(&schema).unwrap(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn dedup_args() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+
+        let expr = greatest.call(vec![col("a"), col("b"), col("a"), col("b") + lit(1), col("b") + lit(1)]);
+
+        assert_eq!(simplify(expr), greatest.call(vec![col("a"), col("b"), col("b") + lit(1)]));
+    }
+
+    #[test]
+    fn do_not_dedup_volatile_args() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+
+        let expr = greatest.call(vec![random(), random()]);
+
+        assert_eq!(simplify(expr.clone()), expr);
+    }
+
+    #[test]
+    fn single_remaining_arg() {
+        let greatest = ScalarUDF::from(GreatestUdf::new());
+
+        assert_eq!(simplify(greatest.call(vec![col("a"), col("a")])), col("a"));
+        assert_eq!(simplify(greatest.call(vec![col("a"), null_i32()])), col("a"));
+        assert_eq!(simplify(greatest.call(vec![greatest.call(vec![col("a"), col("a")]), col("a")])), col("a"));
+
+        // The argument is casted to the type greatest would have returned
+        assert_eq!(
+            simplify(greatest.call(vec![col("a"), lit(ScalarValue::Int64(None)), col("a")])),
+            cast(col("a"), DataType::Int64)
+        );
+    }
+
+    #[tokio::test]
+    async fn simplified_plan_return_same_results() {
+        let (ctx, greatest) = create_context();
+
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(8), None]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![Some(3), Some(2), None, None]));
+
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+
+        ctx.register_batch("t", batch).unwrap();
+
+        let df = ctx.table("t").await.unwrap();
+
+        let df = df.select(vec![
+            greatest.call(vec![greatest.call(vec![col("a"), lit(2)]), null_i32(), col("b"), col("a"), lit(1)]),
+            greatest.call(vec![col("a"), null_i32(), col("a")]),
+        ]).unwrap();
+
+        let results = get_primitive_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![
+            vec![Some(3), Some(2), Some(8), Some(2)],
+            vec![Some(1), None, Some(8), None],
+        ]);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::helpers::Transpose;
+
+/// Results are returned as a matrix where each row corresponds to a column in the DataFrame.
+///
+/// Unlike `find_greatest`, `None` is smaller than any value so it must be skipped explicitly
+pub(crate) fn find_least<T: PartialOrd + Clone + 'static>(results: Vec<Vec<Option<T>>>) -> Vec<Option<T>> {
+    let rows = results.transpose();
+
+    rows.iter().map(|row| {
+        let min: Option<T> = row[0].clone();
+
+        row.iter().fold(min, |acc, x| {
+            match (acc, x.clone()) {
+                (None, x) => x,
+                (acc, None) => acc,
+                (Some(acc), Some(x)) => if x < acc {
+                    Some(x)
+                } else {
+                    Some(acc)
+                }
+            }
+        })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_expected_least_nullable_i8() {
+        let col_a: Vec<Option<i8>> = vec![Some(1), None, Some(-10), Some(4), None, Some(120), Some(7), Some(30)];
+        let col_b: Vec<Option<i8>> = vec![Some(5), None, None, Some(-2), Some(10), Some(1), Some(23), None];
+        let cols = vec![col_a, col_b];
+        let expected = vec![Some(1), None, Some(-10), Some(-2), Some(10), Some(1), Some(7), Some(30)];
+
+        assert_eq!(find_least(cols), expected);
+    }
+
+    #[test]
+    fn test_lists() {
+        let col_a = vec![
+            Some(vec![Some(2), Some(100)]),
+            Some(vec![None, Some(100)]),
+            Some(vec![Some(0), Some(4)]),
+            None,
+        ];
+        let col_b = vec![
+            Some(vec![Some(1), Some(200)]),
+            Some(vec![Some(1), Some(200)]),
+            Some(vec![Some(0)]),
+            Some(vec![]),
+        ];
+
+        assert_eq!(find_least(vec![col_a, col_b]), vec![
+            Some(vec![Some(1), Some(200)]),
+            // None is smaller than any value inside the list
+            Some(vec![None, Some(100)]),
+            // Shorter list is smaller when the prefix is equal
+            Some(vec![Some(0)]),
+            // Null list is skipped
+            Some(vec![]),
+        ]);
+    }
+}
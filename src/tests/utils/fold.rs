@@ -0,0 +1,62 @@
+use crate::helpers::{keep_larger, CompareOptions, KeepOrder};
+use crate::{NanOrdering, NullHandling};
+use datafusion::arrow::array::ArrayRef;
+use datafusion::error::Result;
+use datafusion_common::ScalarValue;
+
+/// Return all the combinations of the compare options
+pub(crate) fn all_compare_options() -> Vec<CompareOptions> {
+    let mut options = vec![];
+
+    for order in [KeepOrder::Larger, KeepOrder::Smaller] {
+        for null_handling in [NullHandling::Skip, NullHandling::Propagate] {
+            for nan_ordering in [NanOrdering::Greatest, NanOrdering::Skip, NanOrdering::Propagate] {
+                options.push(CompareOptions::new(order, null_handling, nan_ordering));
+            }
+        }
+    }
+
+    options
+}
+
+/// The reference implementation of the kernels, folding the arrays pairwise the same way `keep_larger_columnar` did
+///
+/// The arrays and the scalar are mapped with `prepare` first, for example to unpack dictionaries
+pub(crate) fn fold_pairwise(arrays: &[&ArrayRef], scalar: Option<&ScalarValue>, options: CompareOptions, prepare: impl Fn(&ArrayRef) -> ArrayRef) -> ArrayRef {
+    let mut arrays_iter = arrays.iter();
+
+    let mut largest = match scalar {
+        Some(scalar) => {
+            let first = arrays_iter.next().unwrap();
+            keep_larger(prepare(first), prepare(&scalar.to_array_of_size(first.len()).unwrap()), options).unwrap()
+        }
+        None => prepare(arrays_iter.next().unwrap()),
+    };
+
+    for array in arrays_iter {
+        largest = keep_larger(prepare(array), largest, options).unwrap();
+    }
+
+    largest
+}
+
+/// Assert that `kernel` returns the same values as [`fold_pairwise`] for all the compare options,
+/// the output of `kernel` is mapped with `prepare` before comparing, and must have the same type as the arrays
+pub(crate) fn assert_same_as_fold(
+    arrays: &[ArrayRef],
+    scalar: Option<&ScalarValue>,
+    kernel: impl Fn(&[&ArrayRef], Option<&ScalarValue>, CompareOptions) -> Result<Option<ArrayRef>>,
+    prepare: impl Fn(&ArrayRef) -> ArrayRef,
+) {
+    let arrays = arrays.iter().collect::<Vec<_>>();
+
+    for options in all_compare_options() {
+        let expected = fold_pairwise(&arrays, scalar, options, &prepare);
+        let actual = kernel(&arrays, scalar, options).unwrap().expect("should be supported");
+
+        assert_eq!(actual.data_type(), arrays[0].data_type(), "{:?}", options);
+
+        // Compare the debug output, as NaN is not equal to itself
+        assert_eq!(format!("{:?}", prepare(&actual)), format!("{:?}", expected), "{:?}", options);
+    }
+}
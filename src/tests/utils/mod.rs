@@ -1,10 +1,14 @@
 mod get_results;
 mod find_greatest;
+mod find_least;
 mod setup;
 mod debug;
 mod generators;
+mod fold;
 
 pub(crate) use get_results::*;
 pub(crate) use find_greatest::find_greatest;
+pub(crate) use find_least::find_least;
 pub(crate) use setup::*;
 pub(crate) use generators::*;
+pub(crate) use fold::*;
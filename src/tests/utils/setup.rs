@@ -1,23 +1,31 @@
 use datafusion::dataframe::DataFrame;
 use datafusion::prelude::SessionContext;
 use datafusion_expr::{LogicalPlanBuilder, ScalarUDF};
-use crate::GreatestUdf;
+use crate::{GreatestUdf, LeastUdf};
 
 
 /// This creates a context with the greatest UDF registered and returns the context and the UDF
 pub(crate) fn create_context() -> (SessionContext, ScalarUDF) {
+    // create the UDF
+    create_context_with_udf(ScalarUDF::from(GreatestUdf::new()))
+}
+
+/// This creates a context with the least UDF registered and returns the context and the UDF
+pub(crate) fn create_least_context() -> (SessionContext, ScalarUDF) {
+    create_context_with_udf(ScalarUDF::from(LeastUdf::new()))
+}
+
+/// This creates a context with the given UDF registered and returns the context and the UDF
+pub(crate) fn create_context_with_udf(udf: ScalarUDF) -> (SessionContext, ScalarUDF) {
     // In this example we register `GreatestUdf` as a user defined function
     // and invoke it via the DataFrame API and SQL
     // declare a new context. In Spark API, this corresponds to a new SparkSession
     let ctx = SessionContext::new();
 
-    // create the UDF
-    let greatest = ScalarUDF::from(GreatestUdf::new());
-
     // register the UDF with the context so it can be invoked by name and from SQL
-    ctx.register_udf(greatest.clone());
+    ctx.register_udf(udf.clone());
 
-    (ctx, greatest)
+    (ctx, udf)
 }
 
 pub(crate) fn create_empty_data_frame(ctx: &SessionContext, create_one_row: bool) -> datafusion_common::Result<DataFrame> {
@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod validation_tests {
-    use crate::tests::utils::{create_context, create_empty_data_frame};
+    use crate::tests::utils::{create_context, create_empty_data_frame, create_least_context};
     use crate::vec_with_lit;
+    use crate::{GreatestIndexUdf, GreatestUdf, LeastUdf};
+    use datafusion::arrow::datatypes::{DataType, Field, UnionFields, UnionMode};
     use datafusion::dataframe::DataFrame;
     use datafusion::error::Result;
-    use datafusion_expr::{lit, Expr};
+    use datafusion_expr::{lit, Expr, ScalarUDFImpl};
+    use std::sync::Arc;
 
     fn call_greatest_with_args(args: Vec<Expr>) -> Result<DataFrame> {
         let (ctx, greatest) = create_context();
@@ -35,10 +38,10 @@ mod validation_tests {
         // TODO - add more types, there are a lot more
         // the first element is the type that is incompatible with the rest
         let cases = vec![
-            vec_with_lit![1i8, 1u8, 1u16, 1u32, 1u64, true, "hello".as_bytes()],
-            vec_with_lit![1i16, 1u16, 1u32, 1u64, true, "hello".as_bytes()],
-            vec_with_lit![1i32, 1u32, 1u64, true, "hello".as_bytes()],
-            vec_with_lit![1i64, 1u64, true, "hello".as_bytes()],
+            vec_with_lit![1i8, true, "hello".as_bytes()],
+            vec_with_lit![1i16, true, "hello".as_bytes()],
+            vec_with_lit![1i32, true, "hello".as_bytes()],
+            vec_with_lit![1i64, true, "hello".as_bytes()],
 
             vec_with_lit![1u8, true, "hello".as_bytes()],
             vec_with_lit![1u16, true, "hello".as_bytes()],
@@ -64,4 +67,115 @@ mod validation_tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_incompatible_types_error_names_the_arguments() {
+        let mut args = (0..15).map(|i| lit(i as i64)).collect::<Vec<_>>();
+        args[3] = lit(true);
+        args[10] = lit("hello".as_bytes());
+
+        let error = call_greatest_with_args(args).unwrap_err();
+        let message = error.message();
+
+        assert!(message.contains("Cannot find a common type for arguments of greatest"), "Error message: {}", message);
+        assert!(message.contains("Argument 4 (Boolean), argument 11 (Binary) can't be coerced to Int64"), "Error message: {}", message);
+        assert!(message.contains("arrow_cast(<argument>, 'Int64')"), "Error message: {}", message);
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_types_error_suggests_the_common_type_of_the_rest() {
+        let error = call_greatest_with_args(vec_with_lit![1i8, true, 2u8, 3u16]).unwrap_err();
+        let message = error.message();
+
+        assert!(message.contains("Argument 2 (Boolean) can't be coerced to Int32"), "Error message: {}", message);
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_types_error_for_least() {
+        let (ctx, least) = create_least_context();
+
+        let df = create_empty_data_frame(&ctx, true).unwrap();
+
+        let error = df.select(vec![least.call(vec_with_lit![true, 1i32, 2i32])]).unwrap_err();
+        let message = error.message();
+
+        assert!(message.contains("Cannot find a common type for arguments of least"), "Error message: {}", message);
+        assert!(message.contains("Argument 1 (Boolean) can't be coerced to Int32"), "Error message: {}", message);
+    }
+
+    fn map_type() -> DataType {
+        let entries = Field::new_struct("entries", vec![Field::new("key", DataType::Utf8, false), Field::new("value", DataType::Int32, true)], false);
+
+        DataType::Map(Arc::new(entries), false)
+    }
+
+    fn union_type() -> DataType {
+        DataType::Union(
+            UnionFields::new(vec![0, 1], vec![Field::new("a", DataType::Int32, true), Field::new("b", DataType::Utf8, true)]),
+            UnionMode::Dense,
+        )
+    }
+
+    #[test]
+    fn test_not_comparable_types() {
+        let udfs: Vec<Box<dyn ScalarUDFImpl>> = vec![Box::new(GreatestUdf::new()), Box::new(LeastUdf::new()), Box::new(GreatestIndexUdf::new())];
+
+        let cases = vec![
+            (map_type(), "as it has no natural order"),
+            (union_type(), "as it has no natural order"),
+            (
+                DataType::Struct(vec![Field::new("id", DataType::Int32, true), Field::new("tags", map_type(), true)].into()),
+                "as the nested field `tags` of type Map",
+            ),
+            (
+                DataType::new_list(DataType::Struct(vec![Field::new("value", union_type(), true)].into()), true),
+                "as the nested field `item.value` of type Union",
+            ),
+            (DataType::Dictionary(Box::new(DataType::Int32), Box::new(map_type())), "as it has no natural order"),
+            (DataType::new_list(DataType::Null, true), "as the nested field `item` of type Null"),
+            (
+                DataType::Struct(vec![Field::new("id", DataType::Int32, true), Field::new("nothing", DataType::Null, true)].into()),
+                "as the nested field `nothing` of type Null",
+            ),
+            (
+                DataType::new_large_list(DataType::new_list(DataType::Null, true), true),
+                "as the nested field `item.item` of type Null",
+            ),
+            (DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Null)), "as it has no natural order"),
+        ];
+
+        for udf in udfs {
+            for (data_type, expected) in &cases {
+                let error = udf.coerce_types(&[data_type.clone(), data_type.clone()]).unwrap_err();
+
+                assert!(error.message().contains(&format!("{} cannot compare arguments of type", udf.name())), "Error message: {}", error.message());
+                assert!(error.message().contains(expected), "Error message: {}", error.message());
+            }
+        }
+    }
+
+    #[test]
+    fn test_comparable_nested_types() {
+        let cases = vec![
+            DataType::new_list(DataType::Int32, true),
+            DataType::new_large_list(DataType::new_list(DataType::Utf8, true), true),
+            DataType::Struct(vec![Field::new("a", DataType::Int32, true), Field::new("b", DataType::new_list(DataType::Float64, true), true)].into()),
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+        ];
+
+        for data_type in cases {
+            let coerced = GreatestUdf::new().coerce_types(&[data_type.clone(), data_type.clone()]).unwrap();
+
+            assert_eq!(coerced, vec![data_type.clone(), data_type]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_not_comparable_types_fail_when_planning() {
+        let (ctx, _) = create_context();
+
+        let error = ctx.sql("SELECT greatest(map(['a'], [1]), map(['b'], [2]))").await.unwrap_err();
+
+        assert!(error.message().contains("greatest cannot compare arguments of type Map"), "Error message: {}", error.message());
+    }
 }
@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod zip_greatest_tests {
+    use crate::tests::utils::{create_context_with_udf, generate_list_values, get_list_result_as_matrix};
+    use crate::{LengthMismatch, NullHandling, ZipGreatestUdf};
+    use datafusion::arrow::array::{ArrayRef, LargeListArray, ListArray, RecordBatch};
+    use datafusion::arrow::datatypes::{DataType, Field, Float64Type, Int32Type, Int64Type};
+    use datafusion::prelude::SessionContext;
+    use datafusion_expr::{col, ScalarUDF};
+    use std::sync::Arc;
+
+    fn create_context_with_lists(columns: Vec<(&str, ArrayRef)>, udf: ZipGreatestUdf) -> (SessionContext, ScalarUDF) {
+        let (ctx, zip_greatest) = create_context_with_udf(ScalarUDF::from(udf));
+
+        ctx.register_batch("t", RecordBatch::try_from_iter(columns).unwrap()).unwrap();
+
+        (ctx, zip_greatest)
+    }
+
+    fn int_lists(lists: Vec<Option<Vec<Option<i32>>>>) -> ArrayRef {
+        Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(lists))
+    }
+
+    fn lists_of_different_lengths() -> Vec<(&'static str, ArrayRef)> {
+        vec![
+            ("a", int_lists(vec![Some(vec![Some(1), Some(5)]), Some(vec![Some(1), None, Some(3)]), None, Some(vec![Some(4)])])),
+            ("b", int_lists(vec![Some(vec![Some(3), Some(2)]), Some(vec![Some(2)]), Some(vec![Some(6)]), None])),
+        ]
+    }
+
+    #[tokio::test]
+    async fn greatest_item_at_each_position() {
+        let a = ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+            Some(vec![Some(1.5), Some(7.0), Some(-3.0)]),
+            Some(vec![None, Some(2.0), Some(f64::INFINITY)]),
+        ]);
+        let b = ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+            Some(vec![Some(2.5), Some(1.0), Some(-4.0)]),
+            Some(vec![Some(0.0), None, Some(5.0)]),
+        ]);
+        let c = ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+            Some(vec![Some(0.0), Some(8.0), Some(-5.0)]),
+            Some(vec![None, None, Some(1.0)]),
+        ]);
+
+        let (ctx, zip_greatest) = create_context_with_lists(
+            vec![("a", Arc::new(a) as ArrayRef), ("b", Arc::new(b)), ("c", Arc::new(c))],
+            ZipGreatestUdf::new(),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b"), col("c")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Float64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![
+            Some(vec![Some(2.5), Some(8.0), Some(-3.0)]),
+            Some(vec![Some(0.0), Some(2.0), Some(f64::INFINITY)]),
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn shorter_lists_are_padded_with_null() {
+        let (ctx, zip_greatest) = create_context_with_lists(lists_of_different_lengths(), ZipGreatestUdf::new());
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![
+            Some(vec![Some(3), Some(5)]),
+            Some(vec![Some(2), None, Some(3)]),
+            Some(vec![Some(6)]),
+            Some(vec![Some(4)]),
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn null_lists_and_padding_are_propagated() {
+        let (ctx, zip_greatest) = create_context_with_lists(
+            lists_of_different_lengths(),
+            ZipGreatestUdf::new().with_null_handling(NullHandling::Propagate),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![
+            Some(vec![Some(3), Some(5)]),
+            Some(vec![Some(2), None, None]),
+            None,
+            None,
+        ]]);
+    }
+
+    #[tokio::test]
+    async fn lists_of_different_lengths_fail_when_configured() {
+        let (ctx, zip_greatest) = create_context_with_lists(
+            lists_of_different_lengths(),
+            ZipGreatestUdf::new().with_length_mismatch(LengthMismatch::Error),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let error = df.collect().await.expect_err("lists of different lengths should fail");
+
+        assert!(error.to_string().contains("Cannot compare lists of different lengths item by item, got 3 and 1 items at row 1"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn lists_of_same_lengths_do_not_fail() {
+        let (ctx, zip_greatest) = create_context_with_lists(
+            vec![
+                ("a", int_lists(vec![Some(vec![Some(1), Some(5)]), None, Some(vec![])])),
+                ("b", int_lists(vec![Some(vec![Some(3), Some(2)]), Some(vec![Some(1)]), Some(vec![])])),
+            ],
+            ZipGreatestUdf::new().with_length_mismatch(LengthMismatch::Error),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(vec![Some(3), Some(5)]), Some(vec![Some(1)]), Some(vec![])]]);
+    }
+
+    #[tokio::test]
+    async fn random_lists_match_the_greatest_item_at_each_position() {
+        let a = generate_list_values::<i32>(500, 0..6, Some(0.1), Some(0.2));
+        let b = generate_list_values::<i32>(500, 0..6, Some(0.1), Some(0.2));
+
+        let (ctx, zip_greatest) = create_context_with_lists(
+            vec![("a", int_lists(a.clone())), ("b", int_lists(b.clone()))],
+            ZipGreatestUdf::new(),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        let results = get_list_result_as_matrix::<Int32Type>(df).await.unwrap();
+
+        let expected = a.iter().zip(&b).map(|(a, b)| {
+            match (a, b) {
+                (None, None) => None,
+                (Some(list), None) | (None, Some(list)) => Some(list.clone()),
+                (Some(a), Some(b)) => Some(
+                    (0..a.len().max(b.len()))
+                        .map(|i| a.get(i).copied().flatten().max(b.get(i).copied().flatten()))
+                        .collect::<Vec<_>>()
+                ),
+            }
+        }).collect::<Vec<_>>();
+
+        assert_eq!(results, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn items_are_coerced_to_common_type() {
+        let large_list: ArrayRef = Arc::new(LargeListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+            Some(vec![Some(10_000_000_000), Some(-1)]),
+        ]));
+
+        let (ctx, zip_greatest) = create_context_with_lists(
+            vec![("a", int_lists(vec![Some(vec![Some(1), Some(5)])])), ("b", large_list)],
+            ZipGreatestUdf::new(),
+        );
+
+        let df = ctx.table("t").await.unwrap().select(vec![zip_greatest.call(vec![col("a"), col("b")])]).unwrap();
+
+        assert_eq!(
+            df.schema().field(0).data_type(),
+            &DataType::List(Arc::new(Field::new_list_field(DataType::Int64, true)))
+        );
+
+        let results = get_list_result_as_matrix::<Int64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(vec![Some(10_000_000_000), Some(5)])]]);
+    }
+
+    #[tokio::test]
+    async fn scalar_lists_from_sql() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(ZipGreatestUdf::new()));
+
+        let df = ctx.sql("SELECT zip_greatest(make_array(1, 5, 3), make_array(4, 2), NULL)").await.unwrap();
+
+        let results = get_list_result_as_matrix::<Int64Type>(df).await.unwrap();
+
+        assert_eq!(results, vec![vec![Some(vec![Some(4), Some(5), Some(3)])]]);
+    }
+
+    #[tokio::test]
+    async fn not_a_list() {
+        let (ctx, _) = create_context_with_udf(ScalarUDF::from(ZipGreatestUdf::new()));
+
+        let error = ctx.sql("SELECT zip_greatest(make_array(1), 1)").await.expect_err("zip_greatest should fail on a non list argument");
+
+        assert!(error.to_string().contains("zip_greatest expects list arguments, got Int64"), "{}", error);
+
+        let error = ctx.sql("SELECT zip_greatest(make_array(1))").await.expect_err("zip_greatest should fail with 1 argument");
+
+        assert!(error.to_string().contains("zip_greatest was called with 1 arguments. It requires at least 2."), "{}", error);
+    }
+}
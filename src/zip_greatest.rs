@@ -0,0 +1,136 @@
+use crate::helpers::{check_comparable, find_coerced_type, impl_compare_options, keep_larger_zip, list_item_type, udf_equals, udf_hash, user_defined_signature, CompareOptions, KeepOrder};
+use crate::{LengthMismatch, NanOrdering, NullHandling};
+use datafusion::arrow::datatypes::{DataType, Field};
+use datafusion::error::Result;
+use datafusion_common::plan_err;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature};
+use std::any::Any;
+use std::sync::Arc;
+
+/// Return a list where the i-th item is the greatest of the i-th items of the list arguments,
+/// for example `zip_greatest([1, 5], [3, 2])` is `[3, 5]`.
+///
+/// Unlike [`GreatestUdf`](crate::GreatestUdf), which compares lists as a whole, items are compared
+/// with the same coercion, null and NaN semantics as the arguments of `greatest`.
+/// Lists of different lengths are padded with NULL items or rejected depending on the [`LengthMismatch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZipGreatestUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+    options: CompareOptions,
+    length_mismatch: LengthMismatch,
+}
+
+impl ZipGreatestUdf {
+    /// Create a new instance of the `ZipGreatestUdf` struct
+    pub fn new() -> Self {
+        Self {
+            signature: user_defined_signature(),
+            aliases: vec![],
+            options: CompareOptions::new(KeepOrder::Larger, NullHandling::default(), NanOrdering::default()),
+            length_mismatch: LengthMismatch::default(),
+        }
+    }
+
+    /// Set how lists of different lengths are handled, by default shorter lists are padded with NULL items
+    pub fn with_length_mismatch(mut self, length_mismatch: LengthMismatch) -> Self {
+        self.length_mismatch = length_mismatch;
+        self
+    }
+
+    /// Return how lists of different lengths are handled
+    pub fn length_mismatch(&self) -> LengthMismatch {
+        self.length_mismatch
+    }
+
+    /// Return the list type all the arguments are coerced to
+    fn coerced_list_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        let mut item_types = vec![];
+
+        for arg_type in arg_types {
+            match list_item_type(arg_type) {
+                Some(item_type) => item_types.push(item_type.clone()),
+
+                // A NULL argument is a NULL list
+                None if arg_type.is_null() => {}
+
+                None => return plan_err!("zip_greatest expects list arguments, got {}", arg_type),
+            }
+        }
+
+        if item_types.is_empty() {
+            return plan_err!("zip_greatest expects at least one list argument, got {:?}", arg_types);
+        }
+
+        let item_type = find_coerced_type(self.name(), &item_types)?;
+
+        Ok(DataType::List(Arc::new(Field::new_list_field(item_type, true))))
+    }
+}
+
+impl_compare_options!(ZipGreatestUdf, "lists and items");
+
+impl Default for ZipGreatestUdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for ZipGreatestUdf {
+    /// We implement as_any so that we can downcast the ScalarUDFImpl trait object
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Return the name of this function
+    fn name(&self) -> &str {
+        "zip_greatest"
+    }
+
+    /// Return the "signature" of this function -- namely what types of arguments it will take
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The return type is a list of the coerced item type
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.coerced_list_type(arg_types)
+    }
+
+    /// Calculate the greatest item at each position of the lists
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        keep_larger_zip(args, self.options, self.length_mismatch)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// All the arguments are coerced to a `List` of the common item type
+    fn coerce_types(&self, input_types: &[DataType]) -> Result<Vec<DataType>> {
+        // make sure that the input types has at least 2 elements, same as greatest
+        if input_types.len() < 2 {
+            return plan_err!(
+                "zip_greatest was called with {} arguments. It requires at least 2.",
+                input_types.len()
+            );
+        }
+
+        let coerced_type = self.coerced_list_type(input_types)?;
+
+        // Fail when planning if the items can't be ordered, instead of when executing
+        if let Some(item_type) = list_item_type(&coerced_type) {
+            check_comparable(self.name(), item_type)?;
+        }
+
+        Ok(vec![coerced_type; input_types.len()])
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        udf_equals(self, other)
+    }
+
+    fn hash_value(&self) -> u64 {
+        udf_hash(self)
+    }
+}